@@ -26,6 +26,15 @@ export class AMM {
         return amountNeeded;
     }
 
+    getTokensForSol(sol: bigint): bigint {
+        const productOfReserves = this.virtualSolReserves * this.virtualTokenReserves;
+        const newVirtualSolReserves = this.virtualSolReserves + sol;
+        const newVirtualTokenReserves = (productOfReserves / newVirtualSolReserves) + 1n;
+        const tokens = this.virtualTokenReserves - newVirtualTokenReserves;
+
+        return tokens;
+    }
+
     applyBuy(token_amount: bigint): BuyResult {
         const final_token_amount = token_amount > this.realTokenReserves ? this.realTokenReserves : token_amount;
         const sol_amount = this.getBuyPrice(final_token_amount);
@@ -63,4 +72,12 @@ export class AMM {
         const sol_received = (this.virtualSolReserves * token_sell_proportion) / scaling_factor;
         return sol_received < this.realSolReserves ? sol_received : this.realSolReserves;
     }
+
+    getTokensForSellSol(sol: bigint, feeBasisPoints: bigint): bigint {
+        const sol_before_fee = (sol * 10000n + (10000n - feeBasisPoints - 1n)) / (10000n - feeBasisPoints);
+        const scaling_factor = this.initialVirtualTokenReserves;
+        const token_sell_proportion = (sol_before_fee * scaling_factor + this.virtualSolReserves - 1n) / this.virtualSolReserves;
+        const remaining_proportion = scaling_factor - token_sell_proportion;
+        return (token_sell_proportion * this.virtualTokenReserves + remaining_proportion - 1n) / remaining_proportion;
+    }
 }
\ No newline at end of file
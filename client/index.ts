@@ -1,2 +1,3 @@
 export * from './amm'
+export * from './pool_amm'
 export * from './utils'
\ No newline at end of file
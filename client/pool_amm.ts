@@ -0,0 +1,105 @@
+import { BuyResult, SellResult } from './amm';
+
+export type LiquidityResult = {
+    lp_amount: bigint;
+    sol_amount: bigint;
+    token_amount: bigint;
+};
+
+const ceilDiv = (a: bigint, b: bigint): bigint => (a + b - 1n) / b;
+
+export class PoolAMM {
+    constructor(
+        public solReserves: bigint,
+        public tokenReserves: bigint,
+        public lpSupply: bigint
+    ) {}
+
+    getBuyPrice(tokens: bigint): bigint {
+        const newTokenReserves = this.tokenReserves - tokens;
+        return ceilDiv(this.solReserves * tokens, newTokenReserves);
+    }
+
+    getTokensForSol(sol: bigint): bigint {
+        const newSolReserves = this.solReserves + sol;
+        return (this.tokenReserves * sol) / newSolReserves;
+    }
+
+    getSellPrice(tokens: bigint): bigint {
+        const newTokenReserves = this.tokenReserves + tokens;
+        return (this.solReserves * tokens) / newTokenReserves;
+    }
+
+    getTokensForSellSol(sol: bigint, feeBasisPoints: bigint): bigint {
+        const sol_before_fee = ceilDiv(sol * 10000n, 10000n - feeBasisPoints);
+        const newSolReserves = this.solReserves - sol_before_fee;
+        return ceilDiv(this.tokenReserves * sol_before_fee, newSolReserves);
+    }
+
+    applyBuy(token_amount: bigint): BuyResult {
+        const sol_amount = this.getBuyPrice(token_amount);
+
+        this.tokenReserves = this.tokenReserves - token_amount;
+        this.solReserves = this.solReserves + sol_amount;
+
+        return {
+            token_amount: token_amount,
+            sol_amount: sol_amount
+        }
+    }
+
+    applySell(token_amount: bigint): SellResult {
+        const sol_amount = this.getSellPrice(token_amount);
+
+        this.tokenReserves = this.tokenReserves + token_amount;
+        this.solReserves = this.solReserves - sol_amount;
+
+        return {
+            token_amount: token_amount,
+            sol_amount: sol_amount
+        }
+    }
+
+    //deposits round up, withdrawals round down, matching the program
+    getDepositAmounts(lp_amount: bigint): [bigint, bigint] {
+        return [
+            ceilDiv(this.solReserves * lp_amount, this.lpSupply),
+            ceilDiv(this.tokenReserves * lp_amount, this.lpSupply),
+        ];
+    }
+
+    getWithdrawAmounts(lp_amount: bigint): [bigint, bigint] {
+        return [
+            (this.solReserves * lp_amount) / this.lpSupply,
+            (this.tokenReserves * lp_amount) / this.lpSupply,
+        ];
+    }
+
+    applyDeposit(lp_amount: bigint): LiquidityResult {
+        const [sol_amount, token_amount] = this.getDepositAmounts(lp_amount);
+
+        this.solReserves = this.solReserves + sol_amount;
+        this.tokenReserves = this.tokenReserves + token_amount;
+        this.lpSupply = this.lpSupply + lp_amount;
+
+        return {
+            lp_amount: lp_amount,
+            sol_amount: sol_amount,
+            token_amount: token_amount
+        }
+    }
+
+    applyWithdraw(lp_amount: bigint): LiquidityResult {
+        const [sol_amount, token_amount] = this.getWithdrawAmounts(lp_amount);
+
+        this.solReserves = this.solReserves - sol_amount;
+        this.tokenReserves = this.tokenReserves - token_amount;
+        this.lpSupply = this.lpSupply - lp_amount;
+
+        return {
+            lp_amount: lp_amount,
+            sol_amount: sol_amount,
+            token_amount: token_amount
+        }
+    }
+}
\ No newline at end of file
@@ -1,3 +1,7 @@
 export const calculateFee = (amount: bigint, fee: number): bigint => {
   return (amount * BigInt(fee)) / 10000n;
 };
+
+export const calculateAmountBeforeFee = (amountWithFee: bigint, fee: number): bigint => {
+  return (amountWithFee * 10000n) / (10000n + BigInt(fee));
+};
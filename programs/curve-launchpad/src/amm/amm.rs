@@ -1,5 +1,7 @@
 use std::fmt;
 
+use crate::amm::CurveKind;
+
 #[derive(Debug)]
 pub struct BuyResult {
     pub token_amount: u64,
@@ -12,13 +14,14 @@ pub struct SellResult {
     pub sol_amount: u64,
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct AMM {
     pub virtual_sol_reserves: u128,
     pub virtual_token_reserves: u128,
     pub real_sol_reserves: u128,
     pub real_token_reserves: u128,
     pub initial_virtual_token_reserves: u128,
+    pub curve_kind: CurveKind,
 }
 
 impl AMM {
@@ -35,20 +38,21 @@ impl AMM {
             real_sol_reserves,
             real_token_reserves,
             initial_virtual_token_reserves,
+            curve_kind: CurveKind::ConstantProduct,
         }
     }
 
-    pub fn get_buy_price(&self, tokens: u128) -> Option<u128> {
-        if tokens == 0 || tokens > self.virtual_token_reserves {
-            return None;
-        }
+    pub fn with_curve_kind(mut self, curve_kind: CurveKind) -> Self {
+        self.curve_kind = curve_kind;
+        self
+    }
 
-        let product_of_reserves = self.virtual_sol_reserves.checked_mul(self.virtual_token_reserves)?;
-        let new_virtual_token_reserves = self.virtual_token_reserves.checked_sub(tokens)?;
-        let new_virtual_sol_reserves = product_of_reserves.checked_div(new_virtual_token_reserves)?.checked_add(1)?;
-        let amount_needed = new_virtual_sol_reserves.checked_sub(self.virtual_sol_reserves)?;
+    pub fn get_buy_price(&self, tokens: u128) -> Option<u128> {
+        self.curve_kind.curve().get_buy_price(self, tokens)
+    }
 
-        Some(amount_needed)
+    pub fn get_tokens_for_sol(&self, sol: u128) -> Option<u128> {
+        self.curve_kind.curve().get_tokens_for_sol(self, sol)
     }
 
     pub fn apply_buy(&mut self, token_amount: u128) -> Option<BuyResult> {
@@ -88,17 +92,24 @@ impl AMM {
     }
 
     pub fn get_sell_price(&self, tokens: u128) -> Option<u128> {
-        if tokens <= 0 || tokens > self.virtual_token_reserves {
-            return None;
-        }
+        self.curve_kind.curve().get_sell_price(self, tokens)
+    }
 
-        let scaling_factor = self.initial_virtual_token_reserves;
+    pub fn get_tokens_for_sell_sol(&self, sol: u128, fee_basis_points: u128) -> Option<u128> {
+        self.curve_kind.curve().get_tokens_for_sell_sol(self, sol, fee_basis_points)
+    }
+
+    //buying out the whole curve and selling it back must fit the u128 math and the u64 amounts
+    pub fn is_within_math_bounds(&self) -> bool {
+        let tokens = self.real_token_reserves;
 
-        let scaled_tokens = tokens.checked_mul(scaling_factor)?;
-        let token_sell_proportion = scaled_tokens.checked_div(self.virtual_token_reserves)?;
-        let sol_received = (self.virtual_sol_reserves.checked_mul(token_sell_proportion)?).checked_div(scaling_factor)?;
+        match self.get_buy_price(tokens) {
+            Some(sol_amount) if sol_amount <= u64::MAX as u128 => {}
+            _ => return false,
+        }
 
-        Some(sol_received.min(self.real_sol_reserves))
+        let mut amm = self.clone();
+        amm.apply_buy(tokens).is_some() && amm.apply_sell(tokens).is_some()
     }
 }
 
@@ -107,8 +118,8 @@ impl fmt::Display for AMM {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(
             f,
-            "AMM {{ virtual_sol_reserves: {}, virtual_token_reserves: {}, real_sol_reserves: {}, real_token_reserves: {}, initial_virtual_token_reserves: {} }}",
-            self.virtual_sol_reserves, self.virtual_token_reserves, self.real_sol_reserves, self.real_token_reserves, self.initial_virtual_token_reserves
+            "AMM {{ virtual_sol_reserves: {}, virtual_token_reserves: {}, real_sol_reserves: {}, real_token_reserves: {}, initial_virtual_token_reserves: {}, curve_kind: {:?} }}",
+            self.virtual_sol_reserves, self.virtual_token_reserves, self.real_sol_reserves, self.real_token_reserves, self.initial_virtual_token_reserves, self.curve_kind
         )
     }
 }
@@ -180,6 +191,30 @@ mod tests {
         assert_eq!(amm.get_sell_price(5000), None); 
     }
 
+    #[test]
+    fn test_get_tokens_for_sell_sol() {
+        let amm = AMM::new(1000, 1000, 500, 500, 1000);
+
+        // Edge case: zero SOL
+        assert_eq!(amm.get_tokens_for_sell_sol(0, 0), None);
+
+        // Normal case
+        assert_eq!(amm.get_tokens_for_sell_sol(90, 0), Some(99));
+        assert_eq!(amm.get_tokens_for_sell_sol(90, 1000), Some(112));
+
+        // Should not exceed real sol reserves
+        assert_eq!(amm.get_tokens_for_sell_sol(501, 0), None);
+
+        // Selling the returned amount always pays out at least the requested SOL after fees
+        for sol in 1..450 {
+            let tokens = amm.get_tokens_for_sell_sol(sol, 50).unwrap();
+            let mut sell_amm = AMM::new(1000, 1000, 500, 500, 1000);
+            let sell_result = sell_amm.apply_sell(tokens).unwrap();
+            let fee = sell_result.sol_amount * 50 / 10000;
+            assert!(sell_result.sol_amount - fee >= sol as u64);
+        }
+    }
+
     #[test]
     fn test_apply_buy() {
         let virtual_sol_reserves = 600;
@@ -220,4 +255,49 @@ mod tests {
         // Edge case: very large token amount
         assert_eq!(amm.get_buy_price(2000), None); 
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_get_tokens_for_sol() {
+        let amm = AMM::new(1000, 1000, 500, 500, 1000);
+
+        assert_eq!(amm.get_tokens_for_sol(0), None);
+
+        // Normal case
+        assert_eq!(amm.get_tokens_for_sol(112), Some(100));
+        assert_eq!(amm.get_tokens_for_sol(111), Some(99));
+
+        // Price of the returned amount never exceeds the SOL spent
+        for sol in 1..2000 {
+            let tokens = amm.get_tokens_for_sol(sol).unwrap();
+            if tokens > 0 {
+                assert!(amm.get_buy_price(tokens).unwrap() <= sol);
+            }
+            assert!(amm.get_buy_price(tokens + 1).unwrap() > sol);
+        }
+    }
+
+    #[test]
+    fn test_is_within_math_bounds() {
+        use crate::amm::CurveKind;
+
+        let kinds = [
+            CurveKind::ConstantProduct,
+            CurveKind::Linear,
+            CurveKind::Exponential,
+            CurveKind::Stepped,
+        ];
+
+        //default params at 6 and 9 decimals
+        for scale in [1u128, 1_000] {
+            for kind in kinds {
+                let amm = AMM::new(30_000_000_000, 1_073_000_000_000_000 * scale, 0, 793_100_000_000_000 * scale, 1_073_000_000_000_000 * scale)
+                    .with_curve_kind(kind);
+                assert!(amm.is_within_math_bounds(), "{:?} at scale {}", kind, scale);
+            }
+        }
+
+        //buying out the curve would cost more SOL than fits in a u64
+        let amm = AMM::new(u64::MAX as u128, 1_000, 0, 999, 1_000);
+        assert!(!amm.is_within_math_bounds());
+    }
+}
@@ -0,0 +1,388 @@
+use anchor_lang::prelude::*;
+
+use crate::amm::AMM;
+
+pub const STEPPED_CURVE_STEPS: u128 = 20;
+const EXP_SCALE: u128 = 1_000_000_000_000_000_000;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq, InitSpace)]
+pub enum CurveKind {
+    #[default]
+    ConstantProduct,
+    Linear,
+    Exponential,
+    Stepped,
+}
+
+impl CurveKind {
+    //every curve kind the program knows about
+    pub const ALL_MASK: u8 = 0b1111;
+
+    pub fn mask(&self) -> u8 {
+        1 << (*self as u8)
+    }
+
+    //an allowed kinds mask needs at least one kind and no unknown bits
+    pub fn is_valid_mask(mask: u8) -> bool {
+        mask != 0 && mask & !Self::ALL_MASK == 0
+    }
+
+    pub fn curve(&self) -> &'static dyn Curve {
+        match self {
+            CurveKind::ConstantProduct => &ConstantProductCurve,
+            CurveKind::Linear => &LinearCurve,
+            CurveKind::Exponential => &ExponentialCurve,
+            CurveKind::Stepped => &SteppedCurve,
+        }
+    }
+}
+
+// Every curve starts at the constant product spot price `initial virtual sol / initial virtual tokens`
+// and is priced off the supply sold so far, `initial_virtual_token_reserves - virtual_token_reserves`.
+// Sell quotes are taken against reserves that already include the tokens being sold, matching `AMM::apply_sell`.
+pub trait Curve {
+    // SOL paid to buy the first `supply` tokens off a fresh curve
+    fn get_cumulative_cost(&self, amm: &AMM, supply: u128) -> Option<u128>;
+
+    fn get_buy_price(&self, amm: &AMM, tokens: u128) -> Option<u128> {
+        if tokens == 0 || tokens > amm.virtual_token_reserves {
+            return None;
+        }
+
+        let supply = get_supply(amm)?;
+        let cost_before = self.get_cumulative_cost(amm, supply)?;
+        let cost_after = self.get_cumulative_cost(amm, supply.checked_add(tokens)?)?;
+
+        cost_after.checked_sub(cost_before)?.checked_add(1)
+    }
+
+    fn get_tokens_for_sol(&self, amm: &AMM, sol: u128) -> Option<u128> {
+        if sol == 0 {
+            return None;
+        }
+
+        let supply = get_supply(amm)?;
+        let max_cost = self.get_cumulative_cost(amm, supply)?.checked_add(sol - 1)?;
+
+        // the largest token amount whose price does not exceed `sol`
+        let (mut low, mut high) = (0, amm.virtual_token_reserves);
+        while low < high {
+            let mid = low + (high - low).div_ceil(2);
+            match self.get_cumulative_cost(amm, supply + mid) {
+                Some(cost) if cost <= max_cost => low = mid,
+                _ => high = mid - 1,
+            }
+        }
+
+        Some(low)
+    }
+
+    fn get_sell_price(&self, amm: &AMM, tokens: u128) -> Option<u128> {
+        if tokens == 0 || tokens > amm.virtual_token_reserves {
+            return None;
+        }
+
+        let supply = get_supply(amm)?;
+        let cost_before = self.get_cumulative_cost(amm, supply)?;
+        let cost_after = self.get_cumulative_cost(amm, supply.checked_add(tokens)?)?;
+
+        Some(cost_after.checked_sub(cost_before)?.min(amm.real_sol_reserves))
+    }
+
+    fn get_tokens_for_sell_sol(&self, amm: &AMM, sol: u128, fee_basis_points: u128) -> Option<u128> {
+        let sol_before_fee = get_sol_before_fee(sol, fee_basis_points)?;
+        if sol_before_fee > amm.real_sol_reserves {
+            return None;
+        }
+
+        let supply = get_supply(amm)?;
+        let cost = self.get_cumulative_cost(amm, supply)?;
+        if cost < sol_before_fee {
+            return None;
+        }
+
+        // the smallest token amount whose sale pays out at least `sol_before_fee`
+        let (mut low, mut high) = (1, supply);
+        while low < high {
+            let mid = low + (high - low) / 2;
+            match self.get_cumulative_cost(amm, supply - mid) {
+                Some(remaining_cost) if cost - remaining_cost >= sol_before_fee => high = mid,
+                _ => low = mid + 1,
+            }
+        }
+
+        Some(low)
+    }
+}
+
+pub struct ConstantProductCurve;
+
+impl Curve for ConstantProductCurve {
+    fn get_cumulative_cost(&self, amm: &AMM, supply: u128) -> Option<u128> {
+        let initial_virtual_sol_reserves = get_initial_virtual_sol_reserves(amm)?;
+        let remaining_tokens = amm.initial_virtual_token_reserves.checked_sub(supply)?;
+
+        initial_virtual_sol_reserves.checked_mul(supply)?.checked_div(remaining_tokens)
+    }
+
+    fn get_buy_price(&self, amm: &AMM, tokens: u128) -> Option<u128> {
+        if tokens == 0 || tokens > amm.virtual_token_reserves {
+            return None;
+        }
+
+        let product_of_reserves = amm.virtual_sol_reserves.checked_mul(amm.virtual_token_reserves)?;
+        let new_virtual_token_reserves = amm.virtual_token_reserves.checked_sub(tokens)?;
+        let new_virtual_sol_reserves = product_of_reserves.checked_div(new_virtual_token_reserves)?.checked_add(1)?;
+        let amount_needed = new_virtual_sol_reserves.checked_sub(amm.virtual_sol_reserves)?;
+
+        Some(amount_needed)
+    }
+
+    fn get_tokens_for_sol(&self, amm: &AMM, sol: u128) -> Option<u128> {
+        if sol == 0 {
+            return None;
+        }
+
+        // inverse of get_buy_price: the largest token amount whose price does not exceed `sol`
+        let product_of_reserves = amm.virtual_sol_reserves.checked_mul(amm.virtual_token_reserves)?;
+        let new_virtual_sol_reserves = amm.virtual_sol_reserves.checked_add(sol)?;
+        let new_virtual_token_reserves = product_of_reserves.checked_div(new_virtual_sol_reserves)?.checked_add(1)?;
+        let tokens = amm.virtual_token_reserves.checked_sub(new_virtual_token_reserves)?;
+
+        Some(tokens)
+    }
+
+    fn get_sell_price(&self, amm: &AMM, tokens: u128) -> Option<u128> {
+        if tokens == 0 || tokens > amm.virtual_token_reserves {
+            return None;
+        }
+
+        let scaling_factor = amm.initial_virtual_token_reserves;
+
+        let scaled_tokens = tokens.checked_mul(scaling_factor)?;
+        let token_sell_proportion = scaled_tokens.checked_div(amm.virtual_token_reserves)?;
+        let sol_received = (amm.virtual_sol_reserves.checked_mul(token_sell_proportion)?).checked_div(scaling_factor)?;
+
+        Some(sol_received.min(amm.real_sol_reserves))
+    }
+
+    fn get_tokens_for_sell_sol(&self, amm: &AMM, sol: u128, fee_basis_points: u128) -> Option<u128> {
+        let sol_before_fee = get_sol_before_fee(sol, fee_basis_points)?;
+        if sol_before_fee > amm.real_sol_reserves {
+            return None;
+        }
+
+        // inverse of get_sell_price: the smallest token amount whose sale pays out at least `sol_before_fee`
+        let scaling_factor = amm.initial_virtual_token_reserves;
+
+        let token_sell_proportion = sol_before_fee.checked_mul(scaling_factor)?.checked_add(amm.virtual_sol_reserves - 1)?.checked_div(amm.virtual_sol_reserves)?;
+        let remaining_proportion = scaling_factor.checked_sub(token_sell_proportion)?;
+        if remaining_proportion == 0 {
+            return None;
+        }
+
+        let tokens = token_sell_proportion.checked_mul(amm.virtual_token_reserves)?.checked_add(remaining_proportion - 1)?.checked_div(remaining_proportion)?;
+
+        Some(tokens)
+    }
+}
+
+// price rises linearly with supply, starting with the same slope as the constant product curve
+pub struct LinearCurve;
+
+impl Curve for LinearCurve {
+    fn get_cumulative_cost(&self, amm: &AMM, supply: u128) -> Option<u128> {
+        let initial_virtual_sol_reserves = get_initial_virtual_sol_reserves(amm)?;
+        let scaling_factor = amm.initial_virtual_token_reserves;
+
+        let area = supply.checked_mul(scaling_factor.checked_add(supply)?)?.checked_div(scaling_factor)?;
+
+        initial_virtual_sol_reserves.checked_mul(area)?.checked_div(scaling_factor)
+    }
+}
+
+// price grows exponentially with supply, starting with the same slope as the constant product curve
+pub struct ExponentialCurve;
+
+impl Curve for ExponentialCurve {
+    fn get_cumulative_cost(&self, amm: &AMM, supply: u128) -> Option<u128> {
+        let initial_virtual_sol_reserves = get_initial_virtual_sol_reserves(amm)?;
+        let scaling_factor = amm.initial_virtual_token_reserves;
+
+        let exponent = supply.checked_mul(2 * EXP_SCALE)?.checked_div(scaling_factor)?;
+
+        initial_virtual_sol_reserves.checked_mul(exp_m1(exponent)?)?.checked_div(2 * EXP_SCALE)
+    }
+}
+
+// price is flat within each of STEPPED_CURVE_STEPS equal supply steps and jumps linearly between them
+pub struct SteppedCurve;
+
+impl Curve for SteppedCurve {
+    fn get_cumulative_cost(&self, amm: &AMM, supply: u128) -> Option<u128> {
+        let initial_virtual_sol_reserves = get_initial_virtual_sol_reserves(amm)?;
+        let scaling_factor = amm.initial_virtual_token_reserves;
+
+        let step_size = scaling_factor.checked_div(STEPPED_CURVE_STEPS)?;
+        if step_size == 0 {
+            return None;
+        }
+
+        let steps = supply / step_size;
+        let completed_steps_supply = step_size.checked_mul(steps)?.checked_mul(steps.saturating_sub(1))?.checked_div(2)?;
+        let current_step_supply = steps.checked_mul(supply - steps * step_size)?;
+        let step_premium = completed_steps_supply
+            .checked_add(current_step_supply)?
+            .checked_mul(2 * step_size)?
+            .checked_div(scaling_factor)?;
+
+        initial_virtual_sol_reserves.checked_mul(supply.checked_add(step_premium)?)?.checked_div(scaling_factor)
+    }
+}
+
+fn get_supply(amm: &AMM) -> Option<u128> {
+    amm.initial_virtual_token_reserves.checked_sub(amm.virtual_token_reserves)
+}
+
+fn get_initial_virtual_sol_reserves(amm: &AMM) -> Option<u128> {
+    amm.virtual_sol_reserves.checked_sub(amm.real_sol_reserves)
+}
+
+// the fee is taken out of the sell proceeds, so the curve has to pay out the grossed up amount
+fn get_sol_before_fee(sol: u128, fee_basis_points: u128) -> Option<u128> {
+    if sol == 0 || fee_basis_points >= 10000 {
+        return None;
+    }
+
+    sol.checked_mul(10000)?.checked_add(10000 - fee_basis_points - 1)?.checked_div(10000 - fee_basis_points)
+}
+
+// e^x - 1 for x scaled by EXP_SCALE, summed as a Taylor series until the terms vanish
+fn exp_m1(x: u128) -> Option<u128> {
+    let mut sum = 0u128;
+    let mut term = x;
+    let mut n = 1u128;
+
+    while term > 0 {
+        sum = sum.checked_add(term)?;
+        n += 1;
+        term = term.checked_mul(x)?.checked_div(EXP_SCALE)?.checked_div(n)?;
+    }
+
+    Some(sum)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::amm::{CurveKind, AMM};
+
+    use super::{exp_m1, EXP_SCALE};
+
+    const CURVE_KINDS: [CurveKind; 4] = [
+        CurveKind::ConstantProduct,
+        CurveKind::Linear,
+        CurveKind::Exponential,
+        CurveKind::Stepped,
+    ];
+
+    fn new_amm(curve_kind: CurveKind) -> AMM {
+        AMM::new(
+            30_000_000_000,
+            1_073_000_000_000_000,
+            0,
+            793_100_000_000_000,
+            1_073_000_000_000_000,
+        )
+        .with_curve_kind(curve_kind)
+    }
+
+    #[test]
+    fn test_exp_m1() {
+        assert_eq!(exp_m1(0), Some(0));
+        assert_eq!(exp_m1(EXP_SCALE), Some(1_718_281_828_459_045_226));
+        assert_eq!(exp_m1(2 * EXP_SCALE), Some(6_389_056_098_930_650_216));
+    }
+
+    #[test]
+    fn test_mask() {
+        assert_eq!(CurveKind::ConstantProduct.mask(), 0b0001);
+        assert_eq!(CurveKind::Linear.mask(), 0b0010);
+        assert_eq!(CurveKind::Exponential.mask(), 0b0100);
+        assert_eq!(CurveKind::Stepped.mask(), 0b1000);
+
+        assert!(CurveKind::is_valid_mask(CurveKind::ConstantProduct.mask()));
+        assert!(CurveKind::is_valid_mask(CurveKind::ALL_MASK));
+        assert!(!CurveKind::is_valid_mask(0));
+        assert!(!CurveKind::is_valid_mask(0b1_0000));
+        assert!(!CurveKind::is_valid_mask(0b1_0001));
+    }
+
+    #[test]
+    fn test_curves_start_at_the_same_price() {
+        for curve_kind in CURVE_KINDS {
+            let amm = new_amm(curve_kind);
+            assert_eq!(amm.get_buy_price(1_000_000), Some(28), "{:?}", curve_kind);
+        }
+    }
+
+    #[test]
+    fn test_cumulative_cost() {
+        let amm = new_amm(CurveKind::ConstantProduct);
+        let supply = 793_100_000_000_000;
+
+        assert_eq!(CurveKind::ConstantProduct.curve().get_cumulative_cost(&amm, supply), Some(85_005_359_056));
+        assert_eq!(CurveKind::Linear.curve().get_cumulative_cost(&amm, supply), Some(38_564_230_814));
+        assert_eq!(CurveKind::Exponential.curve().get_cumulative_cost(&amm, supply), Some(50_781_285_490));
+        assert_eq!(CurveKind::Stepped.curve().get_cumulative_cost(&amm, supply), Some(37_468_266_542));
+    }
+
+    #[test]
+    fn test_buy_and_sell_round_trip() {
+        for curve_kind in CURVE_KINDS {
+            let mut amm = new_amm(curve_kind);
+
+            let buy_result = amm.apply_buy(500_000_000_000_000).unwrap();
+            assert_eq!(buy_result.token_amount, 500_000_000_000_000);
+
+            let sell_result = amm.apply_sell(500_000_000_000_000).unwrap();
+            assert!(sell_result.sol_amount <= buy_result.sol_amount, "{:?}", curve_kind);
+            assert_eq!(amm.virtual_token_reserves, 1_073_000_000_000_000);
+        }
+    }
+
+    #[test]
+    fn test_get_tokens_for_sol() {
+        for curve_kind in CURVE_KINDS {
+            let amm = new_amm(curve_kind);
+
+            for sol in [1, 1_000, 1_000_000_000, 10_000_000_000] {
+                let tokens = amm.get_tokens_for_sol(sol).unwrap();
+                assert!(amm.get_buy_price(tokens).unwrap() <= sol, "{:?}", curve_kind);
+                assert!(amm.get_buy_price(tokens + 1).unwrap() > sol, "{:?}", curve_kind);
+            }
+        }
+    }
+
+    #[test]
+    fn test_get_tokens_for_sell_sol() {
+        for curve_kind in CURVE_KINDS {
+            let mut amm = new_amm(curve_kind);
+            amm.apply_buy(500_000_000_000_000).unwrap();
+
+            for sol in [1, 1_000, 1_000_000_000, 10_000_000_000] {
+                let tokens = amm.get_tokens_for_sell_sol(sol, 50).unwrap();
+                let mut sell_amm = AMM::new(
+                    amm.virtual_sol_reserves,
+                    amm.virtual_token_reserves,
+                    amm.real_sol_reserves,
+                    amm.real_token_reserves,
+                    amm.initial_virtual_token_reserves,
+                )
+                .with_curve_kind(curve_kind);
+                let sell_result = sell_amm.apply_sell(tokens).unwrap();
+                let fee = sell_result.sol_amount * 50 / 10000;
+                assert!(sell_result.sol_amount - fee >= sol as u64, "{:?}", curve_kind);
+            }
+        }
+    }
+}
@@ -1,2 +1,8 @@
+#[allow(clippy::module_inception)]
 pub mod amm;
-pub use amm::*;
\ No newline at end of file
+pub mod curve;
+pub mod pool_amm;
+
+pub use amm::*;
+pub use curve::*;
+pub use pool_amm::*;
@@ -0,0 +1,336 @@
+use std::fmt;
+
+use crate::amm::{BuyResult, SellResult};
+
+#[derive(Debug)]
+pub struct LiquidityResult {
+    pub lp_amount: u64,
+    pub sol_amount: u64,
+    pub token_amount: u64,
+}
+
+#[derive(Debug)]
+pub struct PoolAMM {
+    pub sol_reserves: u128,
+    pub token_reserves: u128,
+    pub lp_supply: u128,
+}
+
+impl PoolAMM {
+    pub fn new(sol_reserves: u128, token_reserves: u128, lp_supply: u128) -> Self {
+        PoolAMM {
+            sol_reserves,
+            token_reserves,
+            lp_supply,
+        }
+    }
+
+    pub fn get_initial_lp_amount(sol: u128, tokens: u128) -> Option<u128> {
+        if sol == 0 || tokens == 0 {
+            return None;
+        }
+
+        Some(isqrt(sol.checked_mul(tokens)?))
+    }
+
+    pub fn get_buy_price(&self, tokens: u128) -> Option<u128> {
+        if tokens == 0 || tokens >= self.token_reserves {
+            return None;
+        }
+
+        let new_token_reserves = self.token_reserves.checked_sub(tokens)?;
+        let amount_needed = ceil_div(self.sol_reserves.checked_mul(tokens)?, new_token_reserves)?;
+
+        Some(amount_needed)
+    }
+
+    pub fn get_tokens_for_sol(&self, sol: u128) -> Option<u128> {
+        if sol == 0 {
+            return None;
+        }
+
+        let new_sol_reserves = self.sol_reserves.checked_add(sol)?;
+        let tokens = self.token_reserves.checked_mul(sol)?.checked_div(new_sol_reserves)?;
+
+        Some(tokens)
+    }
+
+    pub fn get_sell_price(&self, tokens: u128) -> Option<u128> {
+        if tokens == 0 {
+            return None;
+        }
+
+        let new_token_reserves = self.token_reserves.checked_add(tokens)?;
+        let sol_received = self.sol_reserves.checked_mul(tokens)?.checked_div(new_token_reserves)?;
+
+        Some(sol_received)
+    }
+
+    pub fn get_tokens_for_sell_sol(&self, sol: u128, fee_basis_points: u128) -> Option<u128> {
+        if sol == 0 || fee_basis_points >= 10000 {
+            return None;
+        }
+
+        // the fee is taken out of the sell proceeds, so the pool has to pay out the grossed up amount
+        let sol_before_fee = ceil_div(sol.checked_mul(10000)?, 10000 - fee_basis_points)?;
+        if sol_before_fee >= self.sol_reserves {
+            return None;
+        }
+
+        let new_sol_reserves = self.sol_reserves.checked_sub(sol_before_fee)?;
+        let tokens = ceil_div(self.token_reserves.checked_mul(sol_before_fee)?, new_sol_reserves)?;
+
+        Some(tokens)
+    }
+
+    pub fn apply_buy(&mut self, token_amount: u128) -> Option<BuyResult> {
+        let sol_amount = self.get_buy_price(token_amount)?;
+
+        self.token_reserves = self.token_reserves.checked_sub(token_amount)?;
+        self.sol_reserves = self.sol_reserves.checked_add(sol_amount)?;
+
+        Some(BuyResult {
+            token_amount: token_amount as u64,
+            sol_amount: sol_amount as u64,
+        })
+    }
+
+    pub fn apply_sell(&mut self, token_amount: u128) -> Option<SellResult> {
+        let sol_amount = self.get_sell_price(token_amount)?;
+
+        self.token_reserves = self.token_reserves.checked_add(token_amount)?;
+        self.sol_reserves = self.sol_reserves.checked_sub(sol_amount)?;
+
+        Some(SellResult {
+            token_amount: token_amount as u64,
+            sol_amount: sol_amount as u64,
+        })
+    }
+
+    // deposits round up so the pool never hands out a share it was not fully paid for
+    pub fn get_deposit_amounts(&self, lp_amount: u128) -> Option<(u128, u128)> {
+        if lp_amount == 0 || self.lp_supply == 0 {
+            return None;
+        }
+
+        let sol_amount = ceil_div(self.sol_reserves.checked_mul(lp_amount)?, self.lp_supply)?;
+        let token_amount = ceil_div(self.token_reserves.checked_mul(lp_amount)?, self.lp_supply)?;
+
+        Some((sol_amount, token_amount))
+    }
+
+    // withdrawals round down so the remaining holders are never diluted
+    pub fn get_withdraw_amounts(&self, lp_amount: u128) -> Option<(u128, u128)> {
+        if lp_amount == 0 || lp_amount >= self.lp_supply {
+            return None;
+        }
+
+        let sol_amount = self.sol_reserves.checked_mul(lp_amount)?.checked_div(self.lp_supply)?;
+        let token_amount = self.token_reserves.checked_mul(lp_amount)?.checked_div(self.lp_supply)?;
+
+        Some((sol_amount, token_amount))
+    }
+
+    pub fn apply_deposit(&mut self, lp_amount: u128) -> Option<LiquidityResult> {
+        let (sol_amount, token_amount) = self.get_deposit_amounts(lp_amount)?;
+
+        self.sol_reserves = self.sol_reserves.checked_add(sol_amount)?;
+        self.token_reserves = self.token_reserves.checked_add(token_amount)?;
+        self.lp_supply = self.lp_supply.checked_add(lp_amount)?;
+
+        Some(LiquidityResult {
+            lp_amount: lp_amount as u64,
+            sol_amount: sol_amount as u64,
+            token_amount: token_amount as u64,
+        })
+    }
+
+    pub fn apply_withdraw(&mut self, lp_amount: u128) -> Option<LiquidityResult> {
+        let (sol_amount, token_amount) = self.get_withdraw_amounts(lp_amount)?;
+
+        self.sol_reserves = self.sol_reserves.checked_sub(sol_amount)?;
+        self.token_reserves = self.token_reserves.checked_sub(token_amount)?;
+        self.lp_supply = self.lp_supply.checked_sub(lp_amount)?;
+
+        Some(LiquidityResult {
+            lp_amount: lp_amount as u64,
+            sol_amount: sol_amount as u64,
+            token_amount: token_amount as u64,
+        })
+    }
+}
+
+fn ceil_div(numerator: u128, denominator: u128) -> Option<u128> {
+    numerator.checked_add(denominator.checked_sub(1)?)?.checked_div(denominator)
+}
+
+fn isqrt(value: u128) -> u128 {
+    if value < 2 {
+        return value;
+    }
+
+    let mut x = value;
+    let mut y = x.div_ceil(2);
+    while y < x {
+        x = y;
+        y = (x + value / x) / 2;
+    }
+
+    x
+}
+
+impl fmt::Display for PoolAMM {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "PoolAMM {{ sol_reserves: {}, token_reserves: {}, lp_supply: {} }}",
+            self.sol_reserves, self.token_reserves, self.lp_supply
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::amm::PoolAMM;
+
+    use super::isqrt;
+
+    #[test]
+    fn test_isqrt() {
+        assert_eq!(isqrt(0), 0);
+        assert_eq!(isqrt(1), 1);
+        assert_eq!(isqrt(15), 3);
+        assert_eq!(isqrt(16), 4);
+        assert_eq!(isqrt(u128::MAX), u64::MAX as u128);
+    }
+
+    #[test]
+    fn test_get_initial_lp_amount() {
+        assert_eq!(PoolAMM::get_initial_lp_amount(0, 1000), None);
+        assert_eq!(PoolAMM::get_initial_lp_amount(1000, 0), None);
+
+        assert_eq!(PoolAMM::get_initial_lp_amount(100, 400), Some(200));
+        assert_eq!(
+            PoolAMM::get_initial_lp_amount(85_000_000_000, 206_900_000_000_000),
+            Some(4_193_626_115_904)
+        );
+    }
+
+    #[test]
+    fn test_get_buy_price() {
+        let pool = PoolAMM::new(1000, 1000, 1000);
+
+        assert_eq!(pool.get_buy_price(0), None);
+
+        // Normal case
+        assert_eq!(pool.get_buy_price(100), Some(112));
+
+        // Can't drain the pool
+        assert_eq!(pool.get_buy_price(1000), None);
+    }
+
+    #[test]
+    fn test_get_tokens_for_sol() {
+        let pool = PoolAMM::new(1000, 1000, 1000);
+
+        assert_eq!(pool.get_tokens_for_sol(0), None);
+        assert_eq!(pool.get_tokens_for_sol(112), Some(100));
+
+        for sol in 1..2000 {
+            let tokens = pool.get_tokens_for_sol(sol).unwrap();
+            if tokens > 0 {
+                assert!(pool.get_buy_price(tokens).unwrap() <= sol);
+            }
+        }
+    }
+
+    #[test]
+    fn test_get_sell_price() {
+        let pool = PoolAMM::new(1000, 1000, 1000);
+
+        assert_eq!(pool.get_sell_price(0), None);
+        assert_eq!(pool.get_sell_price(100), Some(90));
+        assert_eq!(pool.get_sell_price(u64::MAX as u128), Some(999));
+    }
+
+    #[test]
+    fn test_get_tokens_for_sell_sol() {
+        let pool = PoolAMM::new(1000, 1000, 1000);
+
+        assert_eq!(pool.get_tokens_for_sell_sol(0, 0), None);
+        assert_eq!(pool.get_tokens_for_sell_sol(90, 0), Some(99));
+
+        // Can't drain the pool
+        assert_eq!(pool.get_tokens_for_sell_sol(1000, 0), None);
+
+        for sol in 1..900 {
+            let tokens = pool.get_tokens_for_sell_sol(sol, 50).unwrap();
+            let sol_received = pool.get_sell_price(tokens).unwrap();
+            assert!(sol_received - sol_received * 50 / 10000 >= sol);
+        }
+    }
+
+    #[test]
+    fn test_get_deposit_amounts() {
+        let pool = PoolAMM::new(1000, 3000, 100);
+
+        assert_eq!(pool.get_deposit_amounts(0), None);
+        assert_eq!(pool.get_deposit_amounts(10), Some((100, 300)));
+
+        // Rounds up in favour of the pool
+        assert_eq!(pool.get_deposit_amounts(1), Some((10, 30)));
+        assert_eq!(PoolAMM::new(1001, 3001, 100).get_deposit_amounts(1), Some((11, 31)));
+    }
+
+    #[test]
+    fn test_get_withdraw_amounts() {
+        let pool = PoolAMM::new(1000, 3000, 100);
+
+        assert_eq!(pool.get_withdraw_amounts(0), None);
+        assert_eq!(pool.get_withdraw_amounts(10), Some((100, 300)));
+
+        // Rounds down in favour of the pool
+        assert_eq!(PoolAMM::new(1009, 3009, 100).get_withdraw_amounts(1), Some((10, 30)));
+
+        // Can't withdraw the whole pool
+        assert_eq!(pool.get_withdraw_amounts(100), None);
+    }
+
+    #[test]
+    fn test_apply_deposit_and_withdraw() {
+        let mut pool = PoolAMM::new(1000, 3000, 100);
+
+        let deposit_result = pool.apply_deposit(50).unwrap();
+        assert_eq!(deposit_result.lp_amount, 50);
+        assert_eq!(deposit_result.sol_amount, 500);
+        assert_eq!(deposit_result.token_amount, 1500);
+        assert_eq!(pool.sol_reserves, 1500);
+        assert_eq!(pool.token_reserves, 4500);
+        assert_eq!(pool.lp_supply, 150);
+
+        let withdraw_result = pool.apply_withdraw(50).unwrap();
+        assert_eq!(withdraw_result.lp_amount, 50);
+        assert_eq!(withdraw_result.sol_amount, 500);
+        assert_eq!(withdraw_result.token_amount, 1500);
+        assert_eq!(pool.sol_reserves, 1000);
+        assert_eq!(pool.token_reserves, 3000);
+        assert_eq!(pool.lp_supply, 100);
+    }
+
+    #[test]
+    fn test_apply_buy_and_sell() {
+        let mut pool = PoolAMM::new(1000, 1000, 1000);
+
+        let buy_result = pool.apply_buy(100).unwrap();
+        assert_eq!(buy_result.token_amount, 100);
+        assert_eq!(buy_result.sol_amount, 112);
+        assert_eq!(pool.token_reserves, 900);
+        assert_eq!(pool.sol_reserves, 1112);
+
+        let sell_result = pool.apply_sell(100).unwrap();
+        assert_eq!(sell_result.token_amount, 100);
+        assert_eq!(sell_result.sol_amount, 111);
+        assert_eq!(pool.token_reserves, 1000);
+        assert_eq!(pool.sol_reserves, 1001);
+    }
+}
@@ -1,8 +1,8 @@
 use anchor_lang::{prelude::*, solana_program::system_instruction};
-use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
 
 use crate::{
-    amm, calculate_fee, state::{BondingCurve, Global}, CompleteEvent, CurveLaunchpadError, TradeEvent
+    amm, calculate_fee, get_fee_split, state::{Allowlist, BondingCurve, CreatorVault, Global, UserPurchase}, CompleteEvent, CurveLaunchpadError, InitialBuy, TradeEvent, TradeFeeEvent
 };
 
 #[event_cpi]
@@ -21,7 +21,7 @@ pub struct Buy<'info> {
     #[account(mut)]
     fee_recipient: AccountInfo<'info>,
 
-    mint: Account<'info, Mint>,
+    mint: InterfaceAccount<'info, Mint>,
 
     #[account(
         mut,
@@ -34,63 +34,281 @@ pub struct Buy<'info> {
         mut,
         associated_token::mint = mint,
         associated_token::authority = bonding_curve,
+        associated_token::token_program = token_program,
     )]
-    bonding_curve_token_account: Box<Account<'info, TokenAccount>>,
+    bonding_curve_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: Using seed to validate creator_vault account, curves upgraded from the old layout have no creator
+    #[account(
+        mut,
+        seeds = [CreatorVault::SEED_PREFIX, bonding_curve.creator.as_ref()],
+        bump,
+    )]
+    creator_vault: AccountInfo<'info>,
 
     #[account(
         mut,
         associated_token::mint = mint,
         associated_token::authority = user,
+        associated_token::token_program = token_program,
     )]
-    user_token_account: Box<Account<'info, TokenAccount>>,
+    user_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    //only required while launch protection or the allowlist phase caps purchases
+    #[account(
+        init_if_needed,
+        payer = user,
+        seeds = [
+            UserPurchase::SEED_PREFIX,
+            mint.to_account_info().key.as_ref(),
+            user.to_account_info().key.as_ref(),
+        ],
+        bump,
+        space = 8 + UserPurchase::INIT_SPACE,
+    )]
+    user_purchase: Option<Box<Account<'info, UserPurchase>>>,
 
     system_program: Program<'info, System>,
 
-    token_program: Program<'info, Token>,
+    token_program: Interface<'info, TokenInterface>,
 }
 
 pub fn buy(ctx: Context<Buy>, token_amount: u64, max_sol_cost: u64) -> Result<()> {
+    //only allowlisted wallets can buy until the allowlist phase ends
+    require!(
+        !ctx.accounts.bonding_curve.allowlist.is_active(Clock::get()?.unix_timestamp),
+        CurveLaunchpadError::AllowlistPhaseActive,
+    );
+
+    process_buy(ctx, token_amount, max_sol_cost)
+}
+
+pub fn buy_allowlisted(
+    ctx: Context<Buy>,
+    token_amount: u64,
+    max_sol_cost: u64,
+    max_tokens: u64,
+    proof: Vec<[u8; 32]>,
+) -> Result<()> {
+    //the proof is only needed while the allowlist phase is active
+    let allowlist = ctx.accounts.bonding_curve.allowlist;
+
+    if allowlist.is_active(Clock::get()?.unix_timestamp) {
+        let leaf = Allowlist::get_leaf(ctx.accounts.user.key, max_tokens);
+
+        require!(
+            allowlist.verify(&proof, leaf),
+            CurveLaunchpadError::InvalidAllowlistProof,
+        );
+
+        //a zero cap means the wallet is uncapped
+        require!(
+            max_tokens == 0
+                || get_tokens_bought(&ctx).saturating_add(token_amount) <= max_tokens,
+            CurveLaunchpadError::AllowlistCapExceeded,
+        );
+    }
+
+    process_buy(ctx, token_amount, max_sol_cost)
+}
+
+fn process_buy(ctx: Context<Buy>, token_amount: u64, max_sol_cost: u64) -> Result<()> {
     require!(
         ctx.accounts.global.initialized,
         CurveLaunchpadError::NotInitialized
     );
 
+    //trading is not paused globally or on this curve
+    require!(
+        !ctx.accounts.global.paused && !ctx.accounts.bonding_curve.paused,
+        CurveLaunchpadError::Paused,
+    );
+
     //bonding curve is not complete
     require!(
-        ctx.accounts.bonding_curve.complete == false,
+        !ctx.accounts.bonding_curve.complete,
         CurveLaunchpadError::BondingCurveComplete,
     );
 
+    //trading has opened
+    require!(
+        Clock::get()?.unix_timestamp >= ctx.accounts.bonding_curve.trading_starts_at,
+        CurveLaunchpadError::TradingNotStarted,
+    );
+
+    let targe_token_amount = if ctx.accounts.bonding_curve_token_account.amount < token_amount {
+        ctx.accounts.bonding_curve_token_account.amount
+    } else {
+        token_amount
+    };
+
+    //purchases are tracked while launch protection or the allowlist phase is active
+    let clock = Clock::get()?;
+    ctx.accounts.bonding_curve.launch_window.start(clock.slot);
+    let is_launch_window_active = ctx.accounts.bonding_curve.launch_window.is_active(clock.slot);
+
+    require!(
+        ctx.accounts.user_purchase.is_some()
+            || !(is_launch_window_active
+                || ctx.accounts.bonding_curve.allowlist.is_active(clock.unix_timestamp)),
+        CurveLaunchpadError::UserPurchaseRequired,
+    );
+
+    //cap cumulative buys per wallet while launch protection is active
+    let tokens_bought = get_tokens_bought(&ctx).saturating_add(targe_token_amount);
+
+    if is_launch_window_active {
+        require!(
+            tokens_bought <= ctx.accounts.bonding_curve.launch_window.max_buy_per_wallet,
+            CurveLaunchpadError::MaxBuyPerWalletExceeded,
+        );
+    }
+
+    let buy_accounts = BuyAccounts {
+        user: ctx.accounts.user.to_account_info(),
+        fee_recipient: ctx.accounts.fee_recipient.to_account_info(),
+        creator_vault: ctx.accounts.creator_vault.to_account_info(),
+        mint: ctx.accounts.mint.to_account_info(),
+        bonding_curve_token_account: ctx.accounts.bonding_curve_token_account.to_account_info(),
+        user_token_account: ctx.accounts.user_token_account.to_account_info(),
+        system_program: ctx.accounts.system_program.to_account_info(),
+        token_program: ctx.accounts.token_program.to_account_info(),
+    };
+
+    let buy_events = execute_buy(
+        buy_accounts,
+        &ctx.accounts.global,
+        &mut ctx.accounts.bonding_curve,
+        ctx.bumps.bonding_curve,
+        ctx.accounts.mint.decimals,
+        targe_token_amount,
+        max_sol_cost,
+    )?;
+
+    if let Some(user_purchase) = ctx.accounts.user_purchase.as_mut() {
+        user_purchase.tokens_bought = tokens_bought;
+    }
+
+    emit_cpi!(buy_events.trade);
+    emit_cpi!(buy_events.trade_fee);
+    if let Some(complete) = buy_events.complete {
+        emit_cpi!(complete);
+    }
+
+    Ok(())
+}
+
+fn get_tokens_bought(ctx: &Context<Buy>) -> u64 {
+    ctx.accounts
+        .user_purchase
+        .as_ref()
+        .map_or(0, |user_purchase| user_purchase.tokens_bought)
+}
+
+//accounts a curve buy moves SOL and tokens between
+pub(crate) struct BuyAccounts<'info> {
+    pub user: AccountInfo<'info>,
+    pub fee_recipient: AccountInfo<'info>,
+    pub creator_vault: AccountInfo<'info>,
+    pub mint: AccountInfo<'info>,
+    pub bonding_curve_token_account: AccountInfo<'info>,
+    pub user_token_account: AccountInfo<'info>,
+    pub system_program: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+}
+
+//events for the caller to emit, emit_cpi! needs the caller's context
+pub(crate) struct BuyEvents {
+    pub trade: TradeEvent,
+    pub trade_fee: TradeFeeEvent,
+    pub complete: Option<CompleteEvent>,
+}
+
+//the creator buy at create, shared by create and create_v2. it lands before anyone else can trade,
+//so trading_starts_at doesn't apply, but it counts toward the creator's per-wallet cap like any buy
+pub(crate) fn execute_creator_buy<'info>(
+    accounts: BuyAccounts<'info>,
+    global: &Global,
+    bonding_curve: &mut Account<'info, BondingCurve>,
+    bonding_curve_bump: u8,
+    creator_purchase: Option<&mut Account<'info, UserPurchase>>,
+    decimals: u8,
+    initial_buy: InitialBuy,
+) -> Result<BuyEvents> {
+    let clock = Clock::get()?;
+
+    //a window still waiting for trading to open caps the creator as well, they buy ahead of it
+    let is_launch_window_capped = bonding_curve.launch_window.is_active(clock.slot)
+        || bonding_curve.launch_window.is_pending();
+
+    require!(
+        creator_purchase.is_some()
+            || !(is_launch_window_capped || bonding_curve.allowlist.is_active(clock.unix_timestamp)),
+        CurveLaunchpadError::UserPurchaseRequired,
+    );
+
+    if is_launch_window_capped {
+        require!(
+            initial_buy.initial_buy_token_amount <= bonding_curve.launch_window.max_buy_per_wallet,
+            CurveLaunchpadError::MaxBuyPerWalletExceeded,
+        );
+    }
+
+    let buy_events = execute_buy(
+        accounts,
+        global,
+        bonding_curve,
+        bonding_curve_bump,
+        decimals,
+        initial_buy.initial_buy_token_amount,
+        initial_buy.max_sol_cost,
+    )?;
+
+    //later buys in the launch window or allowlist phase count the initial buy
+    if let Some(creator_purchase) = creator_purchase {
+        creator_purchase.tokens_bought = initial_buy.initial_buy_token_amount;
+    }
+
+    Ok(buy_events)
+}
+
+//prices the buy, moves the SOL and tokens and applies it to the bonding curve,
+//shared by process_buy and execute_creator_buy
+pub(crate) fn execute_buy<'info>(
+    accounts: BuyAccounts<'info>,
+    global: &Global,
+    bonding_curve: &mut Account<'info, BondingCurve>,
+    bonding_curve_bump: u8,
+    decimals: u8,
+    token_amount: u64,
+    max_sol_cost: u64,
+) -> Result<BuyEvents> {
     //invalid fee recipient
     require!(
-        ctx.accounts.fee_recipient.key == &ctx.accounts.global.fee_recipient,
+        accounts.fee_recipient.key == &global.fee_recipient,
         CurveLaunchpadError::InvalidFeeRecipient,
     );
 
     //bonding curve has enough tokens
     require!(
-        ctx.accounts.bonding_curve.real_token_reserves >= token_amount,
+        bonding_curve.real_token_reserves >= token_amount,
         CurveLaunchpadError::InsufficientTokens,
     );
 
     require!(token_amount > 0, CurveLaunchpadError::MinBuy,);
 
-    let targe_token_amount = if ctx.accounts.bonding_curve_token_account.amount < token_amount {
-        ctx.accounts.bonding_curve_token_account.amount
-    } else {
-        token_amount
-    };
-
     let mut amm = amm::amm::AMM::new(
-        ctx.accounts.bonding_curve.virtual_sol_reserves as u128,
-        ctx.accounts.bonding_curve.virtual_token_reserves as u128,
-        ctx.accounts.bonding_curve.real_sol_reserves as u128,
-        ctx.accounts.bonding_curve.real_token_reserves as u128,
-        ctx.accounts.global.initial_virtual_token_reserves as u128,
-    );
-
-    let buy_result = amm.apply_buy(targe_token_amount as u128).unwrap();
-    let fee = calculate_fee(buy_result.sol_amount, ctx.accounts.global.fee_basis_points);
+        bonding_curve.virtual_sol_reserves as u128,
+        bonding_curve.virtual_token_reserves as u128,
+        bonding_curve.real_sol_reserves as u128,
+        bonding_curve.real_token_reserves as u128,
+        bonding_curve.initial_virtual_token_reserves as u128,
+    )
+    .with_curve_kind(bonding_curve.curve_kind);
+
+    let buy_result = amm.apply_buy(token_amount as u128).unwrap();
+    let fee_basis_points = bonding_curve.get_fee_basis_points(buy_result.sol_amount);
+    let fee = calculate_fee(buy_result.sol_amount, fee_basis_points);
     let buy_amount_with_fee = buy_result.sol_amount + fee;
 
     //check if the amount of SOL to transfe plus fee is less than the max_sol_cost
@@ -101,107 +319,152 @@ pub fn buy(ctx: Context<Buy>, token_amount: u64, max_sol_cost: u64) -> Result<()
 
     //check if the user has enough SOL
     require!(
-        ctx.accounts.user.lamports() >= buy_amount_with_fee,
+        accounts.user.lamports() >= buy_amount_with_fee,
         CurveLaunchpadError::InsufficientSOL,
     );
-    
-    // transfer SOL to bonding curve
-    let from_account = &ctx.accounts.user;
-    let to_bonding_curve_account = &ctx.accounts.bonding_curve;
-
-    let transfer_instruction = system_instruction::transfer(
-        from_account.key,
-        to_bonding_curve_account.to_account_info().key,
-        buy_result.sol_amount,
-    );
 
-    anchor_lang::solana_program::program::invoke_signed(
-        &transfer_instruction,
-        &[
-            from_account.to_account_info(),
-            to_bonding_curve_account.to_account_info(),
-            ctx.accounts.system_program.to_account_info(),
-        ],
-        &[],
-    )?;
-
-    //transfer SOL to fee recipient
-    let to_fee_recipient_account = &ctx.accounts.fee_recipient;
-
-    let transfer_instruction = system_instruction::transfer(
-        from_account.key,
-        to_fee_recipient_account.key,
+    let (protocol_fee, creator_fee) = get_fee_split(
+        &bonding_curve.creator,
+        bonding_curve.creator_fee_basis_points,
         fee,
     );
 
-    anchor_lang::solana_program::program::invoke_signed(
-        &transfer_instruction,
-        &[
-            from_account.to_account_info(),
-            to_fee_recipient_account.to_account_info(),
-            ctx.accounts.system_program.to_account_info(),
-        ],
-        &[],
-    )?;
+    //transfer SOL to bonding curve, fee recipient and creator vault
+    for (to_account, amount) in [
+        (bonding_curve.to_account_info(), buy_result.sol_amount),
+        (accounts.fee_recipient.clone(), protocol_fee),
+        (accounts.creator_vault.clone(), creator_fee),
+    ] {
+        if amount == 0 {
+            continue;
+        }
+
+        let transfer_instruction = system_instruction::transfer(
+            accounts.user.key,
+            to_account.key,
+            amount,
+        );
+
+        anchor_lang::solana_program::program::invoke(
+            &transfer_instruction,
+            &[
+                accounts.user.clone(),
+                to_account,
+                accounts.system_program.clone(),
+            ],
+        )?;
+    }
 
     //transfer SPL
-    let cpi_accounts = Transfer {
-        from: ctx
-            .accounts
-            .bonding_curve_token_account
-            .to_account_info()
-            .clone(),
-        to: ctx.accounts.user_token_account.to_account_info().clone(),
-        authority: ctx.accounts.bonding_curve.to_account_info().clone(),
+    let cpi_accounts = TransferChecked {
+        from: accounts.bonding_curve_token_account.clone(),
+        mint: accounts.mint.clone(),
+        to: accounts.user_token_account.clone(),
+        authority: bonding_curve.to_account_info(),
     };
 
     let signer: [&[&[u8]]; 1] = [&[
         BondingCurve::SEED_PREFIX,
-        ctx.accounts.mint.to_account_info().key.as_ref(),
-        &[ctx.bumps.bonding_curve],
+        accounts.mint.key.as_ref(),
+        &[bonding_curve_bump],
     ]];
 
-    token::transfer(
-        CpiContext::new_with_signer(
-            ctx.accounts.token_program.to_account_info(),
-            cpi_accounts,
-            &signer,
-        ),
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(accounts.token_program.clone(), cpi_accounts, &signer),
         buy_result.token_amount,
+        decimals,
     )?;
 
     //apply the buy to the bonding curve
-    let bonding_curve = &mut ctx.accounts.bonding_curve;
     bonding_curve.real_token_reserves = amm.real_token_reserves as u64;
     bonding_curve.real_sol_reserves = amm.real_sol_reserves as u64;
     bonding_curve.virtual_token_reserves = amm.virtual_token_reserves as u64;
     bonding_curve.virtual_sol_reserves = amm.virtual_sol_reserves as u64;
 
-    emit_cpi!(TradeEvent {
-        mint: *ctx.accounts.mint.to_account_info().key,
+    let timestamp = Clock::get()?.unix_timestamp;
+
+    let trade = TradeEvent {
+        mint: *accounts.mint.key,
         sol_amount: buy_result.sol_amount,
         token_amount: buy_result.token_amount,
         is_buy: true,
-        user: *ctx.accounts.user.to_account_info().key,
-        timestamp: Clock::get()?.unix_timestamp,
+        user: *accounts.user.key,
+        timestamp,
         virtual_sol_reserves: bonding_curve.virtual_sol_reserves,
         virtual_token_reserves: bonding_curve.virtual_token_reserves,
         real_sol_reserves: bonding_curve.real_sol_reserves,
         real_token_reserves: bonding_curve.real_token_reserves,
-    });
+    };
+
+    let trade_fee = TradeFeeEvent {
+        mint: *accounts.mint.key,
+        user: *accounts.user.key,
+        creator: bonding_curve.creator,
+        is_buy: true,
+        fee,
+        protocol_fee,
+        creator_fee,
+        timestamp,
+    };
 
+    //metadata is frozen by migrate, buy doesn't carry the metadata or mint authority accounts.
+    //freeze_metadata and freeze_metadata_v2 stay permissionless for curves that are withdrawn
+    let mut complete = None;
     if bonding_curve.real_token_reserves == 0 {
         bonding_curve.complete = true;
 
-        emit_cpi!(CompleteEvent {
-            user: *ctx.accounts.user.to_account_info().key,
-            mint: *ctx.accounts.mint.to_account_info().key,
-            bonding_curve: *ctx.accounts.bonding_curve.to_account_info().key,
-            timestamp: Clock::get()?.unix_timestamp,
+        complete = Some(CompleteEvent {
+            user: *accounts.user.key,
+            mint: *accounts.mint.key,
+            bonding_curve: bonding_curve.key(),
+            timestamp,
         });
     }
 
     msg!("bonding_curve: {:?}", amm);
 
-    Ok(())
+    Ok(BuyEvents {
+        trade,
+        trade_fee,
+        complete,
+    })
+}
+
+pub fn buy_exact_sol_in(ctx: Context<Buy>, sol_amount: u64, min_tokens_out: u64) -> Result<()> {
+    //bonding curve is not complete
+    require!(
+        !ctx.accounts.bonding_curve.complete,
+        CurveLaunchpadError::BondingCurveComplete,
+    );
+
+    //sol_amount includes the fee, only the remainder is spent on the curve
+    let sol_amount_before_fee = ctx.accounts.bonding_curve.get_buy_amount_before_fee(sol_amount);
+
+    let amm = amm::amm::AMM::new(
+        ctx.accounts.bonding_curve.virtual_sol_reserves as u128,
+        ctx.accounts.bonding_curve.virtual_token_reserves as u128,
+        ctx.accounts.bonding_curve.real_sol_reserves as u128,
+        ctx.accounts.bonding_curve.real_token_reserves as u128,
+        ctx.accounts.bonding_curve.initial_virtual_token_reserves as u128,
+    )
+    .with_curve_kind(ctx.accounts.bonding_curve.curve_kind);
+
+    require!(sol_amount_before_fee > 0, CurveLaunchpadError::MinBuy);
+
+    let token_amount = amm
+        .get_tokens_for_sol(sol_amount_before_fee as u128)
+        .ok_or(CurveLaunchpadError::CurveMathOverflow)?
+        .min(amm.real_token_reserves)
+        .min(ctx.accounts.bonding_curve_token_account.amount as u128) as u64;
+
+    //sol_amount has to buy at least one token, otherwise the fee is charged for nothing
+    require!(token_amount > 0, CurveLaunchpadError::MinBuy);
+
+    //check if the amount of tokens bought is at least min_tokens_out
+    require!(
+        token_amount >= min_tokens_out,
+        CurveLaunchpadError::MinTokenOutputExceeded,
+    );
+
+    buy(ctx, token_amount, sol_amount)
 }
@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+
+use crate::{state::CreatorVault, ClaimCreatorFeesEvent, CurveLaunchpadError};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ClaimCreatorFees<'info> {
+    #[account(mut)]
+    creator: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [CreatorVault::SEED_PREFIX, creator.to_account_info().key.as_ref()],
+        bump,
+    )]
+    creator_vault: Box<Account<'info, CreatorVault>>,
+
+    system_program: Program<'info, System>,
+}
+
+pub fn claim_creator_fees(ctx: Context<ClaimCreatorFees>) -> Result<()> {
+    require!(
+        ctx.accounts.creator_vault.creator == *ctx.accounts.creator.to_account_info().key,
+        CurveLaunchpadError::InvalidCreator,
+    );
+
+    //everything above rent has been collected from trades
+    let min_balance = Rent::get()?.minimum_balance(8 + CreatorVault::INIT_SPACE);
+    let amount = ctx.accounts.creator_vault.get_lamports().saturating_sub(min_balance);
+
+    require!(amount > 0, CurveLaunchpadError::InsufficientSOL);
+
+    //transfer SOL to creator from creator vault
+    let from_account = &ctx.accounts.creator_vault;
+    let to_account = &ctx.accounts.creator;
+
+    **from_account.to_account_info().try_borrow_mut_lamports()? -= amount;
+    **to_account.try_borrow_mut_lamports()? += amount;
+
+    emit_cpi!(ClaimCreatorFeesEvent {
+        creator: *ctx.accounts.creator.to_account_info().key,
+        creator_vault: *ctx.accounts.creator_vault.to_account_info().key,
+        amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
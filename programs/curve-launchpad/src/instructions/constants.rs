@@ -1,3 +1,9 @@
 pub const DEFAULT_DECIMALS: u32 = 6;
-pub const DEFAULT_TOKEN_LAMPORTS: u64 = (10 as u64).pow(DEFAULT_DECIMALS);
-pub const DEFAULT_TOKEN_SUPPLY: u64 = 1_000_000_000 * DEFAULT_TOKEN_LAMPORTS;
\ No newline at end of file
+pub const DEFAULT_TOKEN_LAMPORTS: u64 = 10u64.pow(DEFAULT_DECIMALS);
+pub const DEFAULT_TOKEN_SUPPLY: u64 = 1_000_000_000 * DEFAULT_TOKEN_LAMPORTS;
+//what the curve sells, the rest of the supply is kept for the pool at migrate
+pub const DEFAULT_INITIAL_REAL_TOKEN_RESERVES: u64 = 793_100_000 * DEFAULT_TOKEN_LAMPORTS;
+//longest delay set_params_timelock accepts, so queued changes can always land eventually
+pub const MAX_PARAMS_TIMELOCK_SECONDS: i64 = 30 * 24 * 60 * 60;
+//delay upgrade_global turns on for globals that never had a timelock
+pub const DEFAULT_PARAMS_TIMELOCK_SECONDS: i64 = 24 * 60 * 60;
\ No newline at end of file
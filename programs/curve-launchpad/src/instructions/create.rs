@@ -1,5 +1,5 @@
 use crate::{
-    state::{BondingCurve, Global}, CreateEvent, CurveLaunchpadError, DEFAULT_DECIMALS
+    amm::{self, CurveKind}, execute_creator_buy, state::{Allowlist, BondingCurve, CreatorVault, CurveParams, Global, LaunchWindow, UserPurchase}, BuyAccounts, CreateEvent, CurveLaunchpadError, DEFAULT_DECIMALS
 };
 use anchor_lang::prelude::*;
 use anchor_spl::{
@@ -8,18 +8,177 @@ use anchor_spl::{
         create_metadata_accounts_v3, mpl_token_metadata::types::DataV2, CreateMetadataAccountsV3,
         Metadata as Metaplex,
     },
-    token::{
-        self, mint_to, spl_token::instruction::AuthorityType, Mint, MintTo, Token, TokenAccount,
-    },
+    token::{self, mint_to, spl_token::instruction::AuthorityType, Mint, MintTo, Token, TokenAccount},
 };
 
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct InitialBuy {
+    pub initial_buy_token_amount: u64,
+    pub max_sol_cost: u64,
+}
+
+//launch settings shared by create and create_v2, validated against global
+pub(crate) struct LaunchConfig {
+    pub curve_kind: CurveKind,
+    pub curve_params: CurveParams,
+    pub launch_window: LaunchWindow,
+    pub trading_starts_at: i64,
+    pub allowlist: Allowlist,
+}
+
+impl LaunchConfig {
+    pub fn new(
+        global: &Global,
+        curve_kind: CurveKind,
+        curve_params: Option<CurveParams>,
+        launch_window: Option<LaunchWindow>,
+        trading_starts_at: Option<i64>,
+        allowlist: Option<Allowlist>,
+        decimals: Option<u8>,
+    ) -> Result<Self> {
+        //confirm program is initialized
+        require!(
+            global.initialized,
+            CurveLaunchpadError::NotInitialized
+        );
+
+        //confirm launches are not paused
+        require!(
+            !global.paused,
+            CurveLaunchpadError::Paused
+        );
+
+        //confirm curve kind is allowed
+        require!(
+            global.allowed_curve_kinds & curve_kind.mask() != 0,
+            CurveLaunchpadError::CurveKindNotAllowed
+        );
+
+        //per-launch params must fall within the bounds set on global
+        let curve_params = match curve_params {
+            Some(curve_params) => {
+                require!(
+                    curve_params.is_valid()
+                        && curve_params.is_within(&global.min_curve_params, &global.max_curve_params),
+                    CurveLaunchpadError::InvalidCurveParams
+                );
+                curve_params
+            }
+            None => global.default_curve_params(),
+        };
+
+        //confirm decimals are in the allowed range
+        let decimals = decimals.unwrap_or(DEFAULT_DECIMALS as u8);
+        require!(
+            (global.min_decimals..=global.max_decimals).contains(&decimals),
+            CurveLaunchpadError::InvalidDecimals
+        );
+
+        //bounds are checked in DEFAULT_DECIMALS units, the curve runs on the scaled amounts
+        let curve_params = curve_params
+            .scale_to_decimals(decimals)
+            .ok_or(CurveLaunchpadError::InvalidDecimals)?;
+
+        let amm = amm::amm::AMM::new(
+            curve_params.initial_virtual_sol_reserves as u128,
+            curve_params.initial_virtual_token_reserves as u128,
+            0,
+            curve_params.initial_real_token_reserves as u128,
+            curve_params.initial_virtual_token_reserves as u128,
+        )
+        .with_curve_kind(curve_kind);
+
+        //the scaled params have to keep every price calculation on the curve in range
+        require!(
+            amm.is_within_math_bounds(),
+            CurveLaunchpadError::CurveMathOverflow
+        );
+
+        //zero means the curve is tradeable right away
+        let trading_starts_at = trading_starts_at.unwrap_or(0);
+
+        //launch protection can't be backdated, it starts no earlier than the current slot,
+        //on a curve that opens later it starts with the first buy once trading opens
+        let clock = Clock::get()?;
+        let launch_window = match launch_window {
+            Some(launch_window) => {
+                require!(
+                    launch_window.is_valid(),
+                    CurveLaunchpadError::InvalidLaunchWindow
+                );
+                let start_slot = if trading_starts_at > clock.unix_timestamp {
+                    LaunchWindow::PENDING_START_SLOT
+                } else {
+                    launch_window.start_slot.max(clock.slot)
+                };
+                LaunchWindow {
+                    start_slot,
+                    ..launch_window
+                }
+            }
+            None => LaunchWindow::default(),
+        };
+
+        let allowlist = match allowlist {
+            Some(allowlist) => {
+                require!(
+                    allowlist.is_valid(),
+                    CurveLaunchpadError::InvalidAllowlist
+                );
+                allowlist
+            }
+            None => Allowlist::default(),
+        };
+
+        Ok(Self {
+            curve_kind,
+            curve_params,
+            launch_window,
+            trading_starts_at,
+            allowlist,
+        })
+    }
+
+    pub fn apply(&self, bonding_curve: &mut BondingCurve, global: &Global, creator: Pubkey) {
+        bonding_curve.virtual_sol_reserves = self.curve_params.initial_virtual_sol_reserves;
+        bonding_curve.virtual_token_reserves = self.curve_params.initial_virtual_token_reserves;
+        bonding_curve.real_sol_reserves = 0;
+        bonding_curve.real_token_reserves = self.curve_params.initial_real_token_reserves;
+        bonding_curve.token_total_supply = self.curve_params.initial_token_supply;
+        bonding_curve.complete = false;
+        bonding_curve.curve_kind = self.curve_kind;
+        bonding_curve.initial_virtual_token_reserves = self.curve_params.initial_virtual_token_reserves;
+        bonding_curve.initial_virtual_sol_reserves = self.curve_params.initial_virtual_sol_reserves;
+        bonding_curve.initial_real_token_reserves = self.curve_params.initial_real_token_reserves;
+        bonding_curve.fee_basis_points = global.fee_basis_points;
+        bonding_curve.creator = creator;
+        bonding_curve.fee_schedule = global.fee_schedule;
+        bonding_curve.launch_window = self.launch_window;
+        bonding_curve.trading_starts_at = self.trading_starts_at;
+        bonding_curve.allowlist = self.allowlist;
+        bonding_curve.creator_fee_basis_points = global.creator_fee_basis_points;
+    }
+}
+
 #[event_cpi]
 #[derive(Accounts)]
+#[instruction(
+    name: String,
+    symbol: String,
+    uri: String,
+    curve_kind: CurveKind,
+    curve_params: Option<CurveParams>,
+    launch_window: Option<LaunchWindow>,
+    trading_starts_at: Option<i64>,
+    initial_buy: Option<InitialBuy>,
+    allowlist: Option<Allowlist>,
+    decimals: Option<u8>,
+)]
 pub struct Create<'info> {
     #[account(
         init,
         payer = creator,
-        mint::decimals = DEFAULT_DECIMALS as u8,
+        mint::decimals = decimals.unwrap_or(DEFAULT_DECIMALS as u8),
         mint::authority = mint_authority,
         mint::freeze_authority = mint_authority
     )]
@@ -52,6 +211,42 @@ pub struct Create<'info> {
     )]
     bonding_curve_token_account: Box<Account<'info, TokenAccount>>,
 
+    //shared by every launch from the same creator
+    #[account(
+        init_if_needed,
+        payer = creator,
+        seeds = [CreatorVault::SEED_PREFIX, creator.to_account_info().key.as_ref()],
+        bump,
+        space = 8 + CreatorVault::INIT_SPACE,
+    )]
+    creator_vault: Box<Account<'info, CreatorVault>>,
+
+    #[account(
+        init_if_needed,
+        payer = creator,
+        associated_token::mint = mint,
+        associated_token::authority = creator,
+    )]
+    creator_token_account: Box<Account<'info, TokenAccount>>,
+
+    //only required when the creator buys at create while launch protection or the allowlist phase caps purchases
+    #[account(
+        init_if_needed,
+        payer = creator,
+        seeds = [
+            UserPurchase::SEED_PREFIX,
+            mint.to_account_info().key.as_ref(),
+            creator.to_account_info().key.as_ref(),
+        ],
+        bump,
+        space = 8 + UserPurchase::INIT_SPACE,
+    )]
+    creator_purchase: Option<Box<Account<'info, UserPurchase>>>,
+
+    /// CHECK: Using global state to validate fee_recipient account
+    #[account(mut)]
+    fee_recipient: AccountInfo<'info>,
+
     #[account(
         seeds = [Global::SEED_PREFIX],
         bump,
@@ -83,12 +278,30 @@ pub struct Create<'info> {
 }
 
 
-pub fn create(ctx: Context<Create>, name: String, symbol: String, uri: String) -> Result<()> {
-    //confirm program is initialized
-    require!(
-        ctx.accounts.global.initialized,
-        CurveLaunchpadError::NotInitialized
-    );
+#[allow(clippy::too_many_arguments)]
+pub fn create(
+    ctx: Context<Create>,
+    name: String,
+    symbol: String,
+    uri: String,
+    curve_kind: CurveKind,
+    curve_params: Option<CurveParams>,
+    launch_window: Option<LaunchWindow>,
+    trading_starts_at: Option<i64>,
+    initial_buy: Option<InitialBuy>,
+    allowlist: Option<Allowlist>,
+    decimals: Option<u8>,
+) -> Result<()> {
+    let launch_config = LaunchConfig::new(
+        &ctx.accounts.global,
+        curve_kind,
+        curve_params,
+        launch_window,
+        trading_starts_at,
+        allowlist,
+        decimals,
+    )?;
+    let curve_params = launch_config.curve_params;
 
     msg!("create::BondingCurve::get_lamports: {:?}", &ctx.accounts.bonding_curve.get_lamports());
 
@@ -132,7 +345,7 @@ pub fn create(ctx: Context<Create>, name: String, symbol: String, uri: String) -
             },
             &signer,
         ),
-        ctx.accounts.global.initial_token_supply,
+        curve_params.initial_token_supply,
     )?;
 
     //remove mint_authority
@@ -146,13 +359,25 @@ pub fn create(ctx: Context<Create>, name: String, symbol: String, uri: String) -
     );
     token::set_authority(cpi_context, AuthorityType::MintTokens, None)?;
 
-    let bonding_curve = &mut ctx.accounts.bonding_curve;
-    bonding_curve.virtual_sol_reserves = ctx.accounts.global.initial_virtual_sol_reserves;
-    bonding_curve.virtual_token_reserves = ctx.accounts.global.initial_virtual_token_reserves;
-    bonding_curve.real_sol_reserves = 0;
-    bonding_curve.real_token_reserves = ctx.accounts.global.initial_real_token_reserves;
-    bonding_curve.token_total_supply = ctx.accounts.global.initial_token_supply;
-    bonding_curve.complete = false;
+    //remove freeze_authority
+    let cpi_context = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        token::SetAuthority {
+            current_authority: ctx.accounts.mint_authority.to_account_info(),
+            account_or_mint: ctx.accounts.mint.to_account_info(),
+        },
+        &signer,
+    );
+    token::set_authority(cpi_context, AuthorityType::FreezeAccount, None)?;
+
+    launch_config.apply(
+        &mut ctx.accounts.bonding_curve,
+        &ctx.accounts.global,
+        *ctx.accounts.creator.to_account_info().key,
+    );
+
+    let creator_vault = &mut ctx.accounts.creator_vault;
+    creator_vault.creator = *ctx.accounts.creator.to_account_info().key;
 
     emit_cpi!(CreateEvent {
         name,
@@ -161,7 +386,38 @@ pub fn create(ctx: Context<Create>, name: String, symbol: String, uri: String) -
         mint: *ctx.accounts.mint.to_account_info().key,
         bonding_curve: *ctx.accounts.bonding_curve.to_account_info().key,
         creator: *ctx.accounts.creator.to_account_info().key,
+        curve_kind,
     });
 
+    //the creator buy lands before anyone else can trade, see execute_creator_buy
+    if let Some(initial_buy) = initial_buy {
+        let buy_accounts = BuyAccounts {
+            user: ctx.accounts.creator.to_account_info(),
+            fee_recipient: ctx.accounts.fee_recipient.to_account_info(),
+            creator_vault: ctx.accounts.creator_vault.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
+            bonding_curve_token_account: ctx.accounts.bonding_curve_token_account.to_account_info(),
+            user_token_account: ctx.accounts.creator_token_account.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            token_program: ctx.accounts.token_program.to_account_info(),
+        };
+
+        let buy_events = execute_creator_buy(
+            buy_accounts,
+            &ctx.accounts.global,
+            &mut ctx.accounts.bonding_curve,
+            ctx.bumps.bonding_curve,
+            ctx.accounts.creator_purchase.as_deref_mut(),
+            ctx.accounts.mint.decimals,
+            initial_buy,
+        )?;
+
+        emit_cpi!(buy_events.trade);
+        emit_cpi!(buy_events.trade_fee);
+        if let Some(complete) = buy_events.complete {
+            emit_cpi!(complete);
+        }
+    }
+
     Ok(())
 }
@@ -0,0 +1,289 @@
+use crate::{
+    amm::CurveKind,
+    execute_creator_buy,
+    state::{Allowlist, BondingCurve, CreatorVault, CurveParams, Global, LaunchWindow, UserPurchase},
+    BuyAccounts, CreateEvent, InitialBuy, LaunchConfig, DEFAULT_DECIMALS,
+};
+use anchor_lang::{prelude::*, solana_program::system_instruction};
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_2022::{self, spl_token_2022::instruction::AuthorityType, Token2022},
+    token_2022_extensions::{
+        spl_pod::optional_keys::OptionalNonZeroPubkey,
+        spl_token_metadata_interface::state::TokenMetadata, token_metadata_initialize,
+        TokenMetadataInitialize,
+    },
+    token_interface::{mint_to, Mint, MintTo, TokenAccount},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(
+    name: String,
+    symbol: String,
+    uri: String,
+    curve_kind: CurveKind,
+    curve_params: Option<CurveParams>,
+    launch_window: Option<LaunchWindow>,
+    trading_starts_at: Option<i64>,
+    initial_buy: Option<InitialBuy>,
+    allowlist: Option<Allowlist>,
+    decimals: Option<u8>,
+)]
+pub struct CreateV2<'info> {
+    //the metadata lives on the mint itself, so no Metaplex account is needed
+    #[account(
+        init,
+        payer = creator,
+        mint::decimals = decimals.unwrap_or(DEFAULT_DECIMALS as u8),
+        mint::authority = mint_authority,
+        mint::freeze_authority = mint_authority,
+        mint::token_program = token_program,
+        extensions::metadata_pointer::authority = mint_authority,
+        extensions::metadata_pointer::metadata_address = mint,
+    )]
+    mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(mut)]
+    creator: Signer<'info>,
+
+    /// CHECK: Using seed to validate mint_authority account
+    #[account(
+        seeds=[b"mint-authority"],
+        bump,
+    )]
+    mint_authority: AccountInfo<'info>,
+
+    #[account(
+        init,
+        payer = creator,
+        seeds = [BondingCurve::SEED_PREFIX, mint.to_account_info().key.as_ref()],
+        bump,
+        space = 8 + BondingCurve::INIT_SPACE,
+    )]
+    bonding_curve: Box<Account<'info, BondingCurve>>,
+
+    #[account(
+        init_if_needed,
+        payer = creator,
+        associated_token::mint = mint,
+        associated_token::authority = bonding_curve,
+        associated_token::token_program = token_program,
+    )]
+    bonding_curve_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    //shared by every launch from the same creator
+    #[account(
+        init_if_needed,
+        payer = creator,
+        seeds = [CreatorVault::SEED_PREFIX, creator.to_account_info().key.as_ref()],
+        bump,
+        space = 8 + CreatorVault::INIT_SPACE,
+    )]
+    creator_vault: Box<Account<'info, CreatorVault>>,
+
+    #[account(
+        init_if_needed,
+        payer = creator,
+        associated_token::mint = mint,
+        associated_token::authority = creator,
+        associated_token::token_program = token_program,
+    )]
+    creator_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    //only required when the creator buys at create while launch protection or the allowlist phase caps purchases
+    #[account(
+        init_if_needed,
+        payer = creator,
+        seeds = [
+            UserPurchase::SEED_PREFIX,
+            mint.to_account_info().key.as_ref(),
+            creator.to_account_info().key.as_ref(),
+        ],
+        bump,
+        space = 8 + UserPurchase::INIT_SPACE,
+    )]
+    creator_purchase: Option<Box<Account<'info, UserPurchase>>>,
+
+    /// CHECK: Using global state to validate fee_recipient account
+    #[account(mut)]
+    fee_recipient: AccountInfo<'info>,
+
+    #[account(
+        seeds = [Global::SEED_PREFIX],
+        bump,
+    )]
+    global: Box<Account<'info, Global>>,
+
+    system_program: Program<'info, System>,
+
+    token_program: Program<'info, Token2022>,
+
+    associated_token_program: Program<'info, AssociatedToken>,
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn create_v2(
+    ctx: Context<CreateV2>,
+    name: String,
+    symbol: String,
+    uri: String,
+    curve_kind: CurveKind,
+    curve_params: Option<CurveParams>,
+    launch_window: Option<LaunchWindow>,
+    trading_starts_at: Option<i64>,
+    initial_buy: Option<InitialBuy>,
+    allowlist: Option<Allowlist>,
+    decimals: Option<u8>,
+) -> Result<()> {
+    let launch_config = LaunchConfig::new(
+        &ctx.accounts.global,
+        curve_kind,
+        curve_params,
+        launch_window,
+        trading_starts_at,
+        allowlist,
+        decimals,
+    )?;
+    let curve_params = launch_config.curve_params;
+
+    let seeds = &["mint-authority".as_bytes(), &[ctx.bumps.mint_authority]];
+    let signer = [&seeds[..]];
+
+    //the token program reallocs the mint for the metadata, the creator tops up the rent first
+    let token_metadata = TokenMetadata {
+        update_authority: OptionalNonZeroPubkey::try_from(Some(*ctx.accounts.mint_authority.key))?,
+        mint: *ctx.accounts.mint.to_account_info().key,
+        name: name.clone(),
+        symbol: symbol.clone(),
+        uri: uri.clone(),
+        additional_metadata: vec![],
+    };
+
+    let mint_info = ctx.accounts.mint.to_account_info();
+    let new_size = mint_info.data_len() + token_metadata.tlv_size_of()?;
+    let lamports_needed = Rent::get()?
+        .minimum_balance(new_size)
+        .saturating_sub(mint_info.lamports());
+
+    if lamports_needed > 0 {
+        let transfer_instruction = system_instruction::transfer(
+            ctx.accounts.creator.key,
+            mint_info.key,
+            lamports_needed,
+        );
+
+        anchor_lang::solana_program::program::invoke(
+            &transfer_instruction,
+            &[
+                ctx.accounts.creator.to_account_info(),
+                mint_info.clone(),
+                ctx.accounts.system_program.to_account_info(),
+            ],
+        )?;
+    }
+
+    token_metadata_initialize(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TokenMetadataInitialize {
+                token_program_id: ctx.accounts.token_program.to_account_info(),
+                metadata: mint_info.clone(),
+                update_authority: ctx.accounts.mint_authority.to_account_info(),
+                mint_authority: ctx.accounts.mint_authority.to_account_info(),
+                mint: mint_info.clone(),
+            },
+            &signer,
+        ),
+        name.clone(),
+        symbol.clone(),
+        uri.clone(),
+    )?;
+
+    //mint tokens to bonding_curve_token_account
+    mint_to(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            MintTo {
+                authority: ctx.accounts.mint_authority.to_account_info(),
+                to: ctx.accounts.bonding_curve_token_account.to_account_info(),
+                mint: mint_info.clone(),
+            },
+            &signer,
+        ),
+        curve_params.initial_token_supply,
+    )?;
+
+    //remove mint_authority
+    let cpi_context = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        token_2022::SetAuthority {
+            current_authority: ctx.accounts.mint_authority.to_account_info(),
+            account_or_mint: mint_info.clone(),
+        },
+        &signer,
+    );
+    token_2022::set_authority(cpi_context, AuthorityType::MintTokens, None)?;
+
+    //remove freeze_authority
+    let cpi_context = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        token_2022::SetAuthority {
+            current_authority: ctx.accounts.mint_authority.to_account_info(),
+            account_or_mint: mint_info.clone(),
+        },
+        &signer,
+    );
+    token_2022::set_authority(cpi_context, AuthorityType::FreezeAccount, None)?;
+
+    launch_config.apply(
+        &mut ctx.accounts.bonding_curve,
+        &ctx.accounts.global,
+        *ctx.accounts.creator.to_account_info().key,
+    );
+
+    let creator_vault = &mut ctx.accounts.creator_vault;
+    creator_vault.creator = *ctx.accounts.creator.to_account_info().key;
+
+    emit_cpi!(CreateEvent {
+        name,
+        symbol,
+        uri,
+        mint: *ctx.accounts.mint.to_account_info().key,
+        bonding_curve: *ctx.accounts.bonding_curve.to_account_info().key,
+        creator: *ctx.accounts.creator.to_account_info().key,
+        curve_kind,
+    });
+
+    //the creator buy lands before anyone else can trade, see execute_creator_buy
+    if let Some(initial_buy) = initial_buy {
+        let buy_accounts = BuyAccounts {
+            user: ctx.accounts.creator.to_account_info(),
+            fee_recipient: ctx.accounts.fee_recipient.to_account_info(),
+            creator_vault: ctx.accounts.creator_vault.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
+            bonding_curve_token_account: ctx.accounts.bonding_curve_token_account.to_account_info(),
+            user_token_account: ctx.accounts.creator_token_account.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            token_program: ctx.accounts.token_program.to_account_info(),
+        };
+
+        let buy_events = execute_creator_buy(
+            buy_accounts,
+            &ctx.accounts.global,
+            &mut ctx.accounts.bonding_curve,
+            ctx.bumps.bonding_curve,
+            ctx.accounts.creator_purchase.as_deref_mut(),
+            ctx.accounts.mint.decimals,
+            initial_buy,
+        )?;
+
+        emit_cpi!(buy_events.trade);
+        emit_cpi!(buy_events.trade_fee);
+        if let Some(complete) = buy_events.complete {
+            emit_cpi!(complete);
+        }
+    }
+
+    Ok(())
+}
@@ -29,4 +29,70 @@ pub enum CurveLaunchpadError {
     InvalidFeeRecipient,
     #[msg("Invalid Withdraw Authority")]
     InvalidWithdrawAuthority,
+    #[msg("Min Token Output Exceeded")]
+    MinTokenOutputExceeded,
+    #[msg("Max Token Input Exceeded")]
+    MaxTokenInputExceeded,
+    #[msg("Min liquidity is 1 LP Token")]
+    MinLiquidity,
+    #[msg("Curve Kind Not Allowed")]
+    CurveKindNotAllowed,
+    #[msg("Invalid Curve Params")]
+    InvalidCurveParams,
+    #[msg("Bonding Curve Already Upgraded")]
+    BondingCurveAlreadyUpgraded,
+    #[msg("Invalid Fee Basis Points")]
+    InvalidFeeBasisPoints,
+    #[msg("Invalid Creator")]
+    InvalidCreator,
+    #[msg("Invalid Fee Schedule")]
+    InvalidFeeSchedule,
+    #[msg("Invalid Launch Window")]
+    InvalidLaunchWindow,
+    #[msg("Max Buy Per Wallet Exceeded")]
+    MaxBuyPerWalletExceeded,
+    #[msg("Trading Not Started")]
+    TradingNotStarted,
+    #[msg("Invalid Allowlist")]
+    InvalidAllowlist,
+    #[msg("Allowlist Phase Active")]
+    AllowlistPhaseActive,
+    #[msg("Invalid Allowlist Proof")]
+    InvalidAllowlistProof,
+    #[msg("Allowlist Cap Exceeded")]
+    AllowlistCapExceeded,
+    #[msg("Invalid Decimals")]
+    InvalidDecimals,
+    #[msg("Freeze Authority Already Revoked")]
+    FreezeAuthorityAlreadyRevoked,
+    #[msg("Paused")]
+    Paused,
+    #[msg("Timelock Enabled")]
+    TimelockEnabled,
+    #[msg("Invalid Timelock")]
+    InvalidTimelock,
+    #[msg("Timelock Not Elapsed")]
+    TimelockNotElapsed,
+    #[msg("Withdraw Cooldown")]
+    WithdrawCooldown,
+    #[msg("Withdraw Cap Exceeded")]
+    WithdrawCapExceeded,
+    #[msg("Invalid Withdraw Limits")]
+    InvalidWithdrawLimits,
+    #[msg("Invalid Withdraw Destination")]
+    InvalidWithdrawDestination,
+    #[msg("Invalid Withdraw Destinations")]
+    InvalidWithdrawDestinations,
+    #[msg("Global Already Upgraded")]
+    GlobalAlreadyUpgraded,
+    #[msg("User Purchase Required")]
+    UserPurchaseRequired,
+    #[msg("Withdraw Destinations Not Set")]
+    WithdrawDestinationsNotSet,
+    #[msg("Curve Must Migrate")]
+    CurveMustMigrate,
+    #[msg("Curve Math Overflow")]
+    CurveMathOverflow,
+    #[msg("Invalid Curve Kinds")]
+    InvalidCurveKinds,
 }
\ No newline at end of file
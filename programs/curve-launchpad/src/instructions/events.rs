@@ -1,5 +1,10 @@
 use anchor_lang::prelude::*;
 
+use crate::{
+    amm::CurveKind,
+    state::{CurveParams, FeeSchedule, ParamsUpdate, WithdrawDestinations},
+};
+
 #[event]
 pub struct CreateEvent {
     pub name: String,
@@ -8,6 +13,7 @@ pub struct CreateEvent {
     pub mint: Pubkey,
     pub bonding_curve: Pubkey,
     pub creator: Pubkey,
+    pub curve_kind: CurveKind,
 }
 
 #[event]
@@ -24,6 +30,42 @@ pub struct TradeEvent {
     pub real_token_reserves: u64,
 }
 
+#[event]
+pub struct TradeFeeEvent {
+    pub mint: Pubkey,
+    pub user: Pubkey,
+    pub creator: Pubkey,
+    pub is_buy: bool,
+    pub fee: u64,
+    pub protocol_fee: u64,
+    pub creator_fee: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ClaimCreatorFeesEvent {
+    pub creator: Pubkey,
+    pub creator_vault: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SetFeeScheduleEvent {
+    pub fee_schedule: FeeSchedule,
+}
+
+#[event]
+pub struct SetDecimalsRangeEvent {
+    pub min_decimals: u8,
+    pub max_decimals: u8,
+}
+
+#[event]
+pub struct SetCreatorFeeEvent {
+    pub creator_fee_basis_points: u64,
+}
+
 #[event]
 pub struct CompleteEvent {
     pub user: Pubkey,
@@ -41,4 +83,156 @@ pub struct SetParamsEvent {
     pub initial_real_token_reserves: u64,
     pub initial_token_supply: u64,
     pub fee_basis_points: u64,
-}
\ No newline at end of file
+}
+
+#[event]
+pub struct SetAllowedCurveKindsEvent {
+    pub allowed_curve_kinds: u8,
+}
+
+#[event]
+pub struct UpgradeBondingCurveEvent {
+    pub mint: Pubkey,
+    pub bonding_curve: Pubkey,
+    pub old_size: u64,
+    pub new_size: u64,
+}
+
+#[event]
+pub struct SetCurveParamBoundsEvent {
+    pub min_curve_params: CurveParams,
+    pub max_curve_params: CurveParams,
+}
+
+#[event]
+pub struct MigrateEvent {
+    pub user: Pubkey,
+    pub mint: Pubkey,
+    pub bonding_curve: Pubkey,
+    pub pool: Pubkey,
+    pub sol_amount: u64,
+    pub token_amount: u64,
+    pub lp_amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct LiquidityEvent {
+    pub mint: Pubkey,
+    pub pool: Pubkey,
+    pub user: Pubkey,
+    pub lp_amount: u64,
+    pub sol_amount: u64,
+    pub token_amount: u64,
+    pub is_deposit: bool,
+    pub timestamp: i64,
+    pub sol_reserves: u64,
+    pub token_reserves: u64,
+    pub lp_supply: u64,
+}
+#[event]
+pub struct MetadataUpdatedEvent {
+    pub mint: Pubkey,
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub is_mutable: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProposeAuthorityEvent {
+    pub authority: Pubkey,
+    pub pending_authority: Pubkey,
+}
+
+#[event]
+pub struct AcceptAuthorityEvent {
+    pub old_authority: Pubkey,
+    pub new_authority: Pubkey,
+}
+
+#[event]
+pub struct SetFeeConfigEvent {
+    pub fee_recipient: Pubkey,
+    pub fee_basis_points: u64,
+}
+
+#[event]
+pub struct SetCurveDefaultsEvent {
+    pub curve_params: CurveParams,
+}
+
+#[event]
+pub struct SetWithdrawAuthorityEvent {
+    pub old_withdraw_authority: Pubkey,
+    pub withdraw_authority: Pubkey,
+}
+
+#[event]
+pub struct SetPauserEvent {
+    pub pauser: Pubkey,
+}
+
+//bonding_curve is None when the whole program is paused
+#[event]
+pub struct PauseEvent {
+    pub user: Pubkey,
+    pub bonding_curve: Option<Pubkey>,
+    pub paused: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SetParamsTimelockEvent {
+    pub params_timelock_seconds: i64,
+}
+
+#[event]
+pub struct QueueParamsEvent {
+    pub params: ParamsUpdate,
+    pub queued_at: i64,
+    pub execute_after: i64,
+}
+
+#[event]
+pub struct ExecuteParamsEvent {
+    pub params: ParamsUpdate,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CancelParamsEvent {
+    pub params: ParamsUpdate,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct WithdrawEvent {
+    pub mint: Pubkey,
+    pub bonding_curve: Pubkey,
+    pub user: Pubkey,
+    pub recipient: Pubkey,
+    pub sol_amount: u64,
+    pub token_amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SetWithdrawLimitsEvent {
+    pub min_interval_seconds: i64,
+    pub window_seconds: i64,
+    pub window_sol_cap: u64,
+}
+
+#[event]
+pub struct SetWithdrawDestinationsEvent {
+    pub withdraw_destinations: WithdrawDestinations,
+}
+
+#[event]
+pub struct UpgradeGlobalEvent {
+    pub global: Pubkey,
+    pub old_size: u64,
+    pub new_size: u64,
+}
@@ -1,4 +1,7 @@
-use crate::{state::Global, CurveLaunchpadError, DEFAULT_TOKEN_SUPPLY};
+use crate::{
+    amm::CurveKind, state::Global, CurveLaunchpadError, DEFAULT_DECIMALS,
+    DEFAULT_INITIAL_REAL_TOKEN_RESERVES, DEFAULT_TOKEN_SUPPLY, MAX_PARAMS_TIMELOCK_SECONDS,
+};
 use anchor_lang::prelude::*;
 
 
@@ -20,7 +23,7 @@ pub struct Initialize<'info> {
 }
 
 
-pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
+pub fn initialize(ctx: Context<Initialize>, params_timelock_seconds: i64) -> Result<()> {
     let global = &mut ctx.accounts.global;
 
     require!(
@@ -28,14 +31,27 @@ pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
         CurveLaunchpadError::AlreadyInitialized,
     );
 
+    //params changes always go through queue_params
+    require!(
+        params_timelock_seconds > 0 && params_timelock_seconds <= MAX_PARAMS_TIMELOCK_SECONDS,
+        CurveLaunchpadError::InvalidTimelock,
+    );
+
     global.authority = *ctx.accounts.authority.to_account_info().key;
     global.initialized = true;
     global.initial_token_supply = DEFAULT_TOKEN_SUPPLY;
     global.initial_real_sol_reserves = 0;
-    global.initial_real_token_reserves = DEFAULT_TOKEN_SUPPLY;
+    global.initial_real_token_reserves = DEFAULT_INITIAL_REAL_TOKEN_RESERVES;
     global.initial_virtual_sol_reserves = 30_000_000_000;
     global.initial_virtual_token_reserves = 1_073_000_000_000_000;
     global.fee_basis_points = 50;
+    global.creator_fee_basis_points = 0;
+    global.allowed_curve_kinds = CurveKind::ConstantProduct.mask();
+    global.min_curve_params = global.default_curve_params();
+    global.max_curve_params = global.default_curve_params();
+    global.min_decimals = DEFAULT_DECIMALS as u8;
+    global.max_decimals = DEFAULT_DECIMALS as u8;
+    global.params_timelock_seconds = params_timelock_seconds;
 
     msg!("Initialized global state");
 
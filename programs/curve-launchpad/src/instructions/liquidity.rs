@@ -0,0 +1,287 @@
+use anchor_lang::{prelude::*, solana_program::system_instruction};
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{self, Burn, Mint, MintTo, TokenAccount, TokenInterface, TransferChecked},
+};
+
+use crate::{
+    amm::{LiquidityResult, PoolAMM},
+    state::{Global, Pool},
+    CurveLaunchpadError, LiquidityEvent,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct Liquidity<'info> {
+    #[account(mut)]
+    user: Signer<'info>,
+
+    #[account(
+        seeds = [Global::SEED_PREFIX],
+        bump,
+    )]
+    global: Box<Account<'info, Global>>,
+
+    mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [Pool::SEED_PREFIX, mint.to_account_info().key.as_ref()],
+        bump,
+    )]
+    pool: Box<Account<'info, Pool>>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = pool,
+        associated_token::token_program = token_program,
+    )]
+    pool_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [Pool::LP_MINT_SEED_PREFIX, mint.to_account_info().key.as_ref()],
+        bump,
+    )]
+    lp_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = user,
+        associated_token::token_program = token_program,
+    )]
+    user_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = lp_mint,
+        associated_token::authority = user,
+        associated_token::token_program = token_program,
+    )]
+    user_lp_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    associated_token_program: Program<'info, AssociatedToken>,
+
+    system_program: Program<'info, System>,
+
+    token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn add_liquidity(
+    ctx: Context<Liquidity>,
+    lp_amount: u64,
+    max_sol_amount: u64,
+    max_token_amount: u64,
+) -> Result<()> {
+    require!(
+        ctx.accounts.global.initialized,
+        CurveLaunchpadError::NotInitialized
+    );
+
+    require!(lp_amount > 0, CurveLaunchpadError::MinLiquidity);
+
+    let mut pool_amm = PoolAMM::new(
+        ctx.accounts.pool.sol_reserves as u128,
+        ctx.accounts.pool.token_reserves as u128,
+        ctx.accounts.pool.lp_supply as u128,
+    );
+
+    let deposit_result = pool_amm
+        .apply_deposit(lp_amount as u128)
+        .ok_or(CurveLaunchpadError::MinLiquidity)?;
+
+    //confirm the deposit stays within the user's slippage bounds
+    require!(
+        deposit_result.sol_amount <= max_sol_amount,
+        CurveLaunchpadError::MaxSOLCostExceeded,
+    );
+
+    require!(
+        deposit_result.token_amount <= max_token_amount,
+        CurveLaunchpadError::MaxTokenInputExceeded,
+    );
+
+    require!(
+        ctx.accounts.user.lamports() >= deposit_result.sol_amount,
+        CurveLaunchpadError::InsufficientSOL,
+    );
+
+    require!(
+        ctx.accounts.user_token_account.amount >= deposit_result.token_amount,
+        CurveLaunchpadError::InsufficientTokens,
+    );
+
+    //transfer SOL to pool
+    let transfer_instruction = system_instruction::transfer(
+        ctx.accounts.user.key,
+        ctx.accounts.pool.to_account_info().key,
+        deposit_result.sol_amount,
+    );
+
+    anchor_lang::solana_program::program::invoke(
+        &transfer_instruction,
+        &[
+            ctx.accounts.user.to_account_info(),
+            ctx.accounts.pool.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+        ],
+    )?;
+
+    //transfer SPL to pool
+    let cpi_accounts = TransferChecked {
+        from: ctx.accounts.user_token_account.to_account_info().clone(),
+        mint: ctx.accounts.mint.to_account_info(),
+        to: ctx.accounts.pool_token_account.to_account_info().clone(),
+        authority: ctx.accounts.user.to_account_info().clone(),
+    };
+
+    token_interface::transfer_checked(
+        CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts),
+        deposit_result.token_amount,
+        ctx.accounts.mint.decimals,
+    )?;
+
+    //mint LP tokens to user
+    let signer: [&[&[u8]]; 1] = [&[
+        Pool::SEED_PREFIX,
+        ctx.accounts.mint.to_account_info().key.as_ref(),
+        &[ctx.bumps.pool],
+    ]];
+
+    token_interface::mint_to(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            MintTo {
+                mint: ctx.accounts.lp_mint.to_account_info(),
+                to: ctx.accounts.user_lp_token_account.to_account_info(),
+                authority: ctx.accounts.pool.to_account_info(),
+            },
+            &signer,
+        ),
+        deposit_result.lp_amount,
+    )?;
+
+    update_pool(&mut ctx.accounts.pool, &pool_amm);
+    emit_liquidity_event(&ctx, &deposit_result, true)?;
+
+    Ok(())
+}
+
+pub fn remove_liquidity(
+    ctx: Context<Liquidity>,
+    lp_amount: u64,
+    min_sol_amount: u64,
+    min_token_amount: u64,
+) -> Result<()> {
+    require!(
+        ctx.accounts.global.initialized,
+        CurveLaunchpadError::NotInitialized
+    );
+
+    require!(lp_amount > 0, CurveLaunchpadError::MinLiquidity);
+
+    require!(
+        ctx.accounts.user_lp_token_account.amount >= lp_amount,
+        CurveLaunchpadError::InsufficientTokens,
+    );
+
+    let mut pool_amm = PoolAMM::new(
+        ctx.accounts.pool.sol_reserves as u128,
+        ctx.accounts.pool.token_reserves as u128,
+        ctx.accounts.pool.lp_supply as u128,
+    );
+
+    let withdraw_result = pool_amm
+        .apply_withdraw(lp_amount as u128)
+        .ok_or(CurveLaunchpadError::InsufficientTokens)?;
+
+    //confirm the withdrawal stays within the user's slippage bounds
+    require!(
+        withdraw_result.sol_amount >= min_sol_amount,
+        CurveLaunchpadError::MinSOLOutputExceeded,
+    );
+
+    require!(
+        withdraw_result.token_amount >= min_token_amount,
+        CurveLaunchpadError::MinTokenOutputExceeded,
+    );
+
+    //burn LP tokens from user
+    token_interface::burn(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Burn {
+                mint: ctx.accounts.lp_mint.to_account_info(),
+                from: ctx.accounts.user_lp_token_account.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        ),
+        withdraw_result.lp_amount,
+    )?;
+
+    //transfer SPL to user
+    let cpi_accounts = TransferChecked {
+        from: ctx.accounts.pool_token_account.to_account_info().clone(),
+        mint: ctx.accounts.mint.to_account_info(),
+        to: ctx.accounts.user_token_account.to_account_info().clone(),
+        authority: ctx.accounts.pool.to_account_info().clone(),
+    };
+
+    let signer: [&[&[u8]]; 1] = [&[
+        Pool::SEED_PREFIX,
+        ctx.accounts.mint.to_account_info().key.as_ref(),
+        &[ctx.bumps.pool],
+    ]];
+
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+            &signer,
+        ),
+        withdraw_result.token_amount,
+        ctx.accounts.mint.decimals,
+    )?;
+
+    //transfer SOL to user
+    **ctx.accounts.pool.to_account_info().try_borrow_mut_lamports()? -= withdraw_result.sol_amount;
+    **ctx.accounts.user.try_borrow_mut_lamports()? += withdraw_result.sol_amount;
+
+    update_pool(&mut ctx.accounts.pool, &pool_amm);
+    emit_liquidity_event(&ctx, &withdraw_result, false)?;
+
+    Ok(())
+}
+
+fn update_pool(pool: &mut Pool, pool_amm: &PoolAMM) {
+    pool.sol_reserves = pool_amm.sol_reserves as u64;
+    pool.token_reserves = pool_amm.token_reserves as u64;
+    pool.lp_supply = pool_amm.lp_supply as u64;
+}
+
+fn emit_liquidity_event(
+    ctx: &Context<Liquidity>,
+    liquidity_result: &LiquidityResult,
+    is_deposit: bool,
+) -> Result<()> {
+    let pool = &ctx.accounts.pool;
+
+    emit_cpi!(LiquidityEvent {
+        mint: *ctx.accounts.mint.to_account_info().key,
+        pool: *pool.to_account_info().key,
+        user: *ctx.accounts.user.to_account_info().key,
+        lp_amount: liquidity_result.lp_amount,
+        sol_amount: liquidity_result.sol_amount,
+        token_amount: liquidity_result.token_amount,
+        is_deposit,
+        timestamp: Clock::get()?.unix_timestamp,
+        sol_reserves: pool.sol_reserves,
+        token_reserves: pool.token_reserves,
+        lp_supply: pool.lp_supply,
+    });
+
+    Ok(())
+}
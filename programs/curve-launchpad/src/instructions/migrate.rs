@@ -0,0 +1,230 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    metadata::{Metadata as Metaplex, MetadataAccount},
+    token_2022::Token2022,
+    token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+
+use crate::{
+    amm::PoolAMM,
+    freeze_metaplex_metadata, freeze_token_metadata, get_token_metadata,
+    state::{BondingCurve, Global, Pool},
+    CurveLaunchpadError, MigrateEvent, DEFAULT_DECIMALS,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct Migrate<'info> {
+    #[account(mut)]
+    user: Signer<'info>,
+
+    #[account(
+        seeds = [Global::SEED_PREFIX],
+        bump,
+    )]
+    global: Box<Account<'info, Global>>,
+
+    //Token-2022 mints hold their own metadata
+    #[account(mut)]
+    mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// CHECK: Using seed to validate mint_authority account
+    #[account(
+        seeds=[b"mint-authority"],
+        bump,
+    )]
+    mint_authority: AccountInfo<'info>,
+
+    /// CHECK: Using seed to validate metadata account, it is only used for SPL Token mints
+    #[account(
+        mut,
+        seeds = [
+            b"metadata",
+            token_metadata_program.key.as_ref(),
+            mint.to_account_info().key.as_ref()
+        ],
+        seeds::program = token_metadata_program.key(),
+        bump,
+    )]
+    metadata: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [BondingCurve::SEED_PREFIX, mint.to_account_info().key.as_ref()],
+        bump,
+    )]
+    bonding_curve: Box<Account<'info, BondingCurve>>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = bonding_curve,
+        associated_token::token_program = token_program,
+    )]
+    bonding_curve_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        payer = user,
+        seeds = [Pool::SEED_PREFIX, mint.to_account_info().key.as_ref()],
+        bump,
+        space = 8 + Pool::INIT_SPACE,
+    )]
+    pool: Box<Account<'info, Pool>>,
+
+    #[account(
+        init,
+        payer = user,
+        associated_token::mint = mint,
+        associated_token::authority = pool,
+        associated_token::token_program = token_program,
+    )]
+    pool_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        payer = user,
+        seeds = [Pool::LP_MINT_SEED_PREFIX, mint.to_account_info().key.as_ref()],
+        bump,
+        mint::decimals = DEFAULT_DECIMALS as u8,
+        mint::authority = pool,
+        mint::token_program = token_program,
+    )]
+    lp_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    associated_token_program: Program<'info, AssociatedToken>,
+
+    system_program: Program<'info, System>,
+
+    token_program: Interface<'info, TokenInterface>,
+
+    token_metadata_program: Program<'info, Metaplex>,
+}
+
+pub fn migrate(ctx: Context<Migrate>) -> Result<()> {
+    require!(
+        ctx.accounts.global.initialized,
+        CurveLaunchpadError::NotInitialized
+    );
+
+    require!(
+        ctx.accounts.bonding_curve.complete,
+        CurveLaunchpadError::BondingCurveNotComplete,
+    );
+
+    //curves without a pool allocation sold their whole supply and are withdrawn instead
+    require!(
+        ctx.accounts.bonding_curve.has_pool_allocation(),
+        CurveLaunchpadError::InsufficientTokens,
+    );
+
+    //everything left on the curve above rent seeds the pool
+    let min_balance = Rent::get()?.minimum_balance(8 + BondingCurve::INIT_SPACE);
+    let sol_amount = ctx.accounts.bonding_curve.get_lamports().saturating_sub(min_balance);
+    let token_amount = ctx.accounts.bonding_curve_token_account.amount;
+
+    require!(sol_amount > 0, CurveLaunchpadError::InsufficientSOL);
+    require!(token_amount > 0, CurveLaunchpadError::InsufficientTokens);
+
+    //the initial liquidity is never minted, so it stays locked in the pool for good
+    let lp_amount = PoolAMM::get_initial_lp_amount(sol_amount as u128, token_amount as u128)
+        .ok_or(CurveLaunchpadError::InsufficientTokens)? as u64;
+
+    //transfer tokens to pool from bonding curve
+    let cpi_accounts = TransferChecked {
+        from: ctx
+            .accounts
+            .bonding_curve_token_account
+            .to_account_info()
+            .clone(),
+        mint: ctx.accounts.mint.to_account_info(),
+        to: ctx.accounts.pool_token_account.to_account_info().clone(),
+        authority: ctx.accounts.bonding_curve.to_account_info().clone(),
+    };
+
+    let signer: [&[&[u8]]; 1] = [&[
+        BondingCurve::SEED_PREFIX,
+        ctx.accounts.mint.to_account_info().key.as_ref(),
+        &[ctx.bumps.bonding_curve],
+    ]];
+
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+            &signer,
+        ),
+        token_amount,
+        ctx.accounts.mint.decimals,
+    )?;
+
+    //transfer sol to pool from bonding curve
+    let from_account = &ctx.accounts.bonding_curve;
+    let to_account = &ctx.accounts.pool;
+
+    **from_account.to_account_info().try_borrow_mut_lamports()? -= sol_amount;
+    **to_account.to_account_info().try_borrow_mut_lamports()? += sol_amount;
+
+    let bonding_curve = &mut ctx.accounts.bonding_curve;
+    bonding_curve.real_sol_reserves = 0;
+    bonding_curve.real_token_reserves = 0;
+
+    let pool = &mut ctx.accounts.pool;
+    pool.mint = *ctx.accounts.mint.to_account_info().key;
+    pool.lp_mint = *ctx.accounts.lp_mint.to_account_info().key;
+    pool.sol_reserves = sol_amount;
+    pool.token_reserves = token_amount;
+    pool.lp_supply = lp_amount;
+
+    //the pool keeps charging the fee the curve completed with, the creator keeps their share
+    pool.creator = bonding_curve.creator;
+    pool.fee_basis_points = bonding_curve.fee_basis_points;
+    pool.fee_schedule = bonding_curve.fee_schedule;
+    pool.creator_fee_basis_points = bonding_curve.creator_fee_basis_points;
+
+    //the pool trades a token whose metadata can't change, skipped if freeze_metadata already ran
+    let mint_info = ctx.accounts.mint.to_account_info();
+    let mint_authority = ctx.accounts.mint_authority.to_account_info();
+
+    if ctx.accounts.token_program.key() == Token2022::id() {
+        let update_authority: Option<Pubkey> =
+            get_token_metadata(&mint_info)?.update_authority.into();
+
+        if update_authority == Some(*mint_authority.key) {
+            freeze_token_metadata(
+                ctx.accounts.token_program.to_account_info(),
+                mint_info,
+                mint_authority,
+                ctx.bumps.mint_authority,
+            )?;
+        }
+    } else {
+        let update_authority = {
+            let data = ctx.accounts.metadata.try_borrow_data()?;
+            MetadataAccount::try_deserialize(&mut &data[..])?.update_authority
+        };
+
+        if update_authority == *mint_authority.key {
+            freeze_metaplex_metadata(
+                ctx.accounts.token_metadata_program.to_account_info(),
+                ctx.accounts.metadata.to_account_info(),
+                mint_authority,
+                ctx.bumps.mint_authority,
+            )?;
+        }
+    }
+
+    emit_cpi!(MigrateEvent {
+        user: *ctx.accounts.user.to_account_info().key,
+        mint: *ctx.accounts.mint.to_account_info().key,
+        bonding_curve: *ctx.accounts.bonding_curve.to_account_info().key,
+        pool: *ctx.accounts.pool.to_account_info().key,
+        sol_amount,
+        token_amount,
+        lp_amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -3,19 +3,43 @@ pub mod errors;
 pub mod buy;
 pub mod sell;
 pub mod create;
+pub mod create_v2;
 pub mod set_params;
 pub mod constants;
 pub mod events;
 pub mod util;
 pub mod withdraw;
+pub mod migrate;
+pub mod swap;
+pub mod liquidity;
+pub mod upgrade_bonding_curve;
+pub mod upgrade_global;
+pub mod claim_creator_fees;
+pub mod update_metadata;
+pub mod update_metadata_v2;
+pub mod revoke_freeze_authority;
+pub mod pause;
+pub mod timelock;
 
 pub use initialize::*;
 pub use errors::*;
 pub use buy::*;
 pub use sell::*;
 pub use create::*;
+pub use create_v2::*;
 pub use set_params::*;
 pub use constants::*;
 pub use events::*;
 pub use util::*;
-pub use withdraw::*;
\ No newline at end of file
+pub use withdraw::*;
+pub use migrate::*;
+pub use swap::*;
+pub use liquidity::*;
+pub use upgrade_bonding_curve::*;
+pub use upgrade_global::*;
+pub use claim_creator_fees::*;
+pub use update_metadata::*;
+pub use update_metadata_v2::*;
+pub use revoke_freeze_authority::*;
+pub use pause::*;
+pub use timelock::*;
\ No newline at end of file
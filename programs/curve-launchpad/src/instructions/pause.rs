@@ -0,0 +1,81 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    state::{BondingCurve, Global},
+    CurveLaunchpadError, PauseEvent,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetPause<'info> {
+    #[account(
+        mut,
+        seeds = [Global::SEED_PREFIX],
+        bump,
+    )]
+    global: Box<Account<'info, Global>>,
+
+    user: Signer<'info>,
+
+    //pauses a single curve when passed, otherwise the whole program
+    #[account(mut)]
+    bonding_curve: Option<Box<Account<'info, BondingCurve>>>,
+}
+
+//the pauser can halt trading, only the authority can resume it
+pub fn pause(ctx: Context<SetPause>) -> Result<()> {
+    let user = *ctx.accounts.user.to_account_info().key;
+
+    //confirm program is initialized
+    require!(
+        ctx.accounts.global.initialized,
+        CurveLaunchpadError::NotInitialized
+    );
+
+    //confirm user is the pauser or the authority
+    require!(
+        (ctx.accounts.global.pauser != Pubkey::default() && ctx.accounts.global.pauser == user)
+            || ctx.accounts.global.authority == user,
+        CurveLaunchpadError::InvalidAuthority
+    );
+
+    set_paused(ctx, true)
+}
+
+pub fn unpause(ctx: Context<SetPause>) -> Result<()> {
+    //confirm program is initialized
+    require!(
+        ctx.accounts.global.initialized,
+        CurveLaunchpadError::NotInitialized
+    );
+
+    //confirm user is the authority
+    require!(
+        ctx.accounts.global.authority == *ctx.accounts.user.to_account_info().key,
+        CurveLaunchpadError::InvalidAuthority
+    );
+
+    set_paused(ctx, false)
+}
+
+fn set_paused(ctx: Context<SetPause>, paused: bool) -> Result<()> {
+    let bonding_curve = match ctx.accounts.bonding_curve.as_mut() {
+        Some(bonding_curve) => {
+            bonding_curve.paused = paused;
+            Some(*bonding_curve.to_account_info().key)
+        }
+        None => {
+            ctx.accounts.global.paused = paused;
+            None
+        }
+    };
+
+    emit_cpi!(PauseEvent {
+        user: *ctx.accounts.user.to_account_info().key,
+        bonding_curve,
+        paused,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
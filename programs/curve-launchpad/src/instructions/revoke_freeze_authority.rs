@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+    self, spl_token_2022::instruction::AuthorityType, Mint, TokenInterface,
+};
+
+use crate::CurveLaunchpadError;
+
+#[derive(Accounts)]
+pub struct RevokeFreezeAuthority<'info> {
+    user: Signer<'info>,
+
+    #[account(
+        mut,
+        mint::token_program = token_program,
+    )]
+    mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// CHECK: Using seed to validate mint_authority account
+    #[account(
+        seeds=[b"mint-authority"],
+        bump,
+    )]
+    mint_authority: AccountInfo<'info>,
+
+    token_program: Interface<'info, TokenInterface>,
+}
+
+//cleans up mints created before create revoked the freeze authority
+pub fn revoke_freeze_authority(ctx: Context<RevokeFreezeAuthority>) -> Result<()> {
+    require!(
+        ctx.accounts.mint.freeze_authority == Some(*ctx.accounts.mint_authority.key).into(),
+        CurveLaunchpadError::FreezeAuthorityAlreadyRevoked
+    );
+
+    let seeds = &["mint-authority".as_bytes(), &[ctx.bumps.mint_authority]];
+    let signer = [&seeds[..]];
+
+    let cpi_context = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        token_interface::SetAuthority {
+            current_authority: ctx.accounts.mint_authority.to_account_info(),
+            account_or_mint: ctx.accounts.mint.to_account_info(),
+        },
+        &signer,
+    );
+    token_interface::set_authority(cpi_context, AuthorityType::FreezeAccount, None)?;
+
+    Ok(())
+}
@@ -1,8 +1,8 @@
 use crate::{
-    amm, calculate_fee, state::{BondingCurve, Global}, CurveLaunchpadError, TradeEvent
+    amm, calculate_fee, get_fee_split, state::{BondingCurve, CreatorVault, Global}, CurveLaunchpadError, TradeEvent, TradeFeeEvent
 };
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
 
 #[event_cpi]
 #[derive(Accounts)]
@@ -20,7 +20,7 @@ pub struct Sell<'info> {
     #[account(mut)]
     fee_recipient: AccountInfo<'info>,
 
-    mint: Account<'info, Mint>,
+    mint: InterfaceAccount<'info, Mint>,
 
     #[account(
         mut,
@@ -33,28 +33,50 @@ pub struct Sell<'info> {
         mut,
         associated_token::mint = mint,
         associated_token::authority = bonding_curve,
+        associated_token::token_program = token_program,
     )]
-    bonding_curve_token_account: Box<Account<'info, TokenAccount>>,
+    bonding_curve_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: Using seed to validate creator_vault account, curves upgraded from the old layout have no creator
+    #[account(
+        mut,
+        seeds = [CreatorVault::SEED_PREFIX, bonding_curve.creator.as_ref()],
+        bump,
+    )]
+    creator_vault: AccountInfo<'info>,
 
     #[account(
         mut,
         associated_token::mint = mint,
         associated_token::authority = user,
+        associated_token::token_program = token_program,
     )]
-    user_token_account: Box<Account<'info, TokenAccount>>,
+    user_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
 
     system_program: Program<'info, System>,
 
-    token_program: Program<'info, Token>,
+    token_program: Interface<'info, TokenInterface>,
 }
 
 pub fn sell(ctx: Context<Sell>, token_amount: u64, min_sol_output: u64) -> Result<()> {
+    //trading is not paused globally or on this curve
+    require!(
+        !ctx.accounts.global.paused && !ctx.accounts.bonding_curve.paused,
+        CurveLaunchpadError::Paused,
+    );
+
     //check if bonding curve is complete
     require!(
         !ctx.accounts.bonding_curve.complete,
         CurveLaunchpadError::BondingCurveComplete,
     );
 
+    //trading has opened
+    require!(
+        Clock::get()?.unix_timestamp >= ctx.accounts.bonding_curve.trading_starts_at,
+        CurveLaunchpadError::TradingNotStarted,
+    );
+
     //confirm user has enough tokens
     require!(
         ctx.accounts.user_token_account.amount >= token_amount,
@@ -80,11 +102,13 @@ pub fn sell(ctx: Context<Sell>, token_amount: u64, min_sol_output: u64) -> Resul
         ctx.accounts.bonding_curve.virtual_token_reserves as u128,
         ctx.accounts.bonding_curve.real_sol_reserves as u128,
         ctx.accounts.bonding_curve.real_token_reserves as u128,
-        ctx.accounts.global.initial_virtual_token_reserves as u128,
-    );
+        ctx.accounts.bonding_curve.initial_virtual_token_reserves as u128,
+    )
+    .with_curve_kind(ctx.accounts.bonding_curve.curve_kind);
 
     let sell_result = amm.apply_sell(token_amount as u128).unwrap();
-    let fee = calculate_fee(sell_result.sol_amount, ctx.accounts.global.fee_basis_points);
+    let fee_basis_points = ctx.accounts.bonding_curve.get_fee_basis_points(sell_result.sol_amount);
+    let fee = calculate_fee(sell_result.sol_amount, fee_basis_points);
 
     //the fee is subtracted from the sol amount to confirm the user minimum sol output is met
     let sell_amount_minus_fee = sell_result.sol_amount - fee;
@@ -96,8 +120,9 @@ pub fn sell(ctx: Context<Sell>, token_amount: u64, min_sol_output: u64) -> Resul
     );
 
     //transfer SPL
-    let cpi_accounts = Transfer {
+    let cpi_accounts = TransferChecked {
         from: ctx.accounts.user_token_account.to_account_info().clone(),
+        mint: ctx.accounts.mint.to_account_info(),
         to: ctx
             .accounts
             .bonding_curve_token_account
@@ -106,27 +131,28 @@ pub fn sell(ctx: Context<Sell>, token_amount: u64, min_sol_output: u64) -> Resul
         authority: ctx.accounts.user.to_account_info().clone(),
     };
 
-    token::transfer(
+    token_interface::transfer_checked(
         CpiContext::new_with_signer(
             ctx.accounts.token_program.to_account_info(),
             cpi_accounts,
             &[],
         ),
         sell_result.token_amount,
+        ctx.accounts.mint.decimals,
     )?;
 
-    //transfer SOL back to user
-    //TODO: check if this is correct
+    //transfer SOL to user, fee recipient and creator vault, the curve pays out sol_amount in total
+    let (protocol_fee, creator_fee) = get_fee_split(
+        &ctx.accounts.bonding_curve.creator,
+        ctx.accounts.bonding_curve.creator_fee_basis_points,
+        fee,
+    );
     let from_account = &ctx.accounts.bonding_curve;
-    let to_account = &ctx.accounts.user;
 
     **from_account.to_account_info().try_borrow_mut_lamports()? -= sell_result.sol_amount;
-    **to_account.try_borrow_mut_lamports()? += sell_result.sol_amount;
-
-    //transfer fee to fee recipient
-    **from_account.to_account_info().try_borrow_mut_lamports()? -= fee;
-    **ctx.accounts.fee_recipient.try_borrow_mut_lamports()? += fee;
-
+    **ctx.accounts.user.try_borrow_mut_lamports()? += sell_amount_minus_fee;
+    **ctx.accounts.fee_recipient.try_borrow_mut_lamports()? += protocol_fee;
+    **ctx.accounts.creator_vault.try_borrow_mut_lamports()? += creator_fee;
 
     let bonding_curve = &mut ctx.accounts.bonding_curve;
     bonding_curve.real_token_reserves = amm.real_token_reserves as u64;
@@ -147,5 +173,51 @@ pub fn sell(ctx: Context<Sell>, token_amount: u64, min_sol_output: u64) -> Resul
         real_token_reserves: bonding_curve.real_token_reserves,
     });
 
+    emit_cpi!(TradeFeeEvent {
+        mint: *ctx.accounts.mint.to_account_info().key,
+        user: *ctx.accounts.user.to_account_info().key,
+        creator: bonding_curve.creator,
+        is_buy: false,
+        fee,
+        protocol_fee,
+        creator_fee,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
     Ok(())
 }
+
+pub fn sell_exact_sol_out(ctx: Context<Sell>, sol_amount: u64, max_tokens_in: u64) -> Result<()> {
+    //check if bonding curve is complete
+    require!(
+        !ctx.accounts.bonding_curve.complete,
+        CurveLaunchpadError::BondingCurveComplete,
+    );
+
+    let amm = amm::amm::AMM::new(
+        ctx.accounts.bonding_curve.virtual_sol_reserves as u128,
+        ctx.accounts.bonding_curve.virtual_token_reserves as u128,
+        ctx.accounts.bonding_curve.real_sol_reserves as u128,
+        ctx.accounts.bonding_curve.real_token_reserves as u128,
+        ctx.accounts.bonding_curve.initial_virtual_token_reserves as u128,
+    )
+    .with_curve_kind(ctx.accounts.bonding_curve.curve_kind);
+
+    //confirm bonding curve has enough SOL to pay out sol_amount after the fee
+    let sol_amount_before_fee = ctx
+        .accounts
+        .bonding_curve
+        .get_sell_amount_before_fee(sol_amount)
+        .ok_or(CurveLaunchpadError::InsufficientSOL)?;
+    let token_amount = amm
+        .get_tokens_for_sell_sol(sol_amount_before_fee as u128, 0)
+        .ok_or(CurveLaunchpadError::InsufficientSOL)? as u64;
+
+    //confirm the tokens needed do not exceed max_tokens_in
+    require!(
+        token_amount <= max_tokens_in,
+        CurveLaunchpadError::MaxTokenInputExceeded,
+    );
+
+    sell(ctx, token_amount, sol_amount)
+}
@@ -1,4 +1,12 @@
-use crate::{state::Global, CurveLaunchpadError, SetParamsEvent};
+use crate::{
+    amm::CurveKind,
+    state::{CurveParams, FeeSchedule, Global, WithdrawDestinations, WithdrawLimits},
+    AcceptAuthorityEvent, CurveLaunchpadError, ProposeAuthorityEvent, SetAllowedCurveKindsEvent,
+    SetCreatorFeeEvent, SetCurveDefaultsEvent, SetCurveParamBoundsEvent, SetDecimalsRangeEvent,
+    SetFeeConfigEvent, SetFeeScheduleEvent, SetParamsEvent, SetParamsTimelockEvent,
+    SetPauserEvent, SetWithdrawAuthorityEvent, SetWithdrawDestinationsEvent,
+    SetWithdrawLimitsEvent, MAX_PARAMS_TIMELOCK_SECONDS,
+};
 use anchor_lang::prelude::*;
 
 #[event_cpi]
@@ -16,6 +24,7 @@ pub struct SetParams<'info> {
     system_program: Program<'info, System>,
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn set_params(
     ctx: Context<SetParams>,
     fee_recipient: Pubkey,
@@ -39,7 +48,22 @@ pub fn set_params(
         global.authority == *ctx.accounts.user.to_account_info().key,
         CurveLaunchpadError::InvalidAuthority
     );
-    
+
+    //these params can only change through queue_params once the timelock is enabled
+    require!(
+        global.params_timelock_seconds == 0,
+        CurveLaunchpadError::TimelockEnabled
+    );
+
+    validate_fee_config(&fee_recipient, fee_basis_points)?;
+    validate_withdraw_authority(&withdraw_authority)?;
+    validate_curve_defaults(&CurveParams {
+        initial_virtual_token_reserves,
+        initial_virtual_sol_reserves,
+        initial_real_token_reserves,
+        initial_token_supply,
+    })?;
+
     global.fee_recipient = fee_recipient;
     global.initial_virtual_token_reserves = initial_virtual_token_reserves;
     global.initial_virtual_sol_reserves = initial_virtual_sol_reserves;
@@ -60,3 +84,568 @@ pub fn set_params(
 
     Ok(())
 }
+
+pub fn set_allowed_curve_kinds(ctx: Context<SetParams>, allowed_curve_kinds: u8) -> Result<()> {
+    let global = &mut ctx.accounts.global;
+
+    //confirm program is initialized
+    require!(
+        global.initialized,
+        CurveLaunchpadError::NotInitialized
+    );
+
+    //confirm user is the authority
+    require!(
+        global.authority == *ctx.accounts.user.to_account_info().key,
+        CurveLaunchpadError::InvalidAuthority
+    );
+
+    //these params can only change through queue_params once the timelock is enabled
+    require!(
+        global.params_timelock_seconds == 0,
+        CurveLaunchpadError::TimelockEnabled
+    );
+
+    validate_allowed_curve_kinds(allowed_curve_kinds)?;
+
+    global.allowed_curve_kinds = allowed_curve_kinds;
+
+    emit_cpi!(SetAllowedCurveKindsEvent {
+        allowed_curve_kinds,
+    });
+
+    Ok(())
+}
+
+pub fn set_curve_param_bounds(
+    ctx: Context<SetParams>,
+    min_curve_params: CurveParams,
+    max_curve_params: CurveParams,
+) -> Result<()> {
+    let global = &mut ctx.accounts.global;
+
+    //confirm program is initialized
+    require!(
+        global.initialized,
+        CurveLaunchpadError::NotInitialized
+    );
+
+    //confirm user is the authority
+    require!(
+        global.authority == *ctx.accounts.user.to_account_info().key,
+        CurveLaunchpadError::InvalidAuthority
+    );
+
+    //these params can only change through queue_params once the timelock is enabled
+    require!(
+        global.params_timelock_seconds == 0,
+        CurveLaunchpadError::TimelockEnabled
+    );
+
+    validate_curve_param_bounds(&min_curve_params, &max_curve_params)?;
+
+    global.min_curve_params = min_curve_params;
+    global.max_curve_params = max_curve_params;
+
+    emit_cpi!(SetCurveParamBoundsEvent {
+        min_curve_params,
+        max_curve_params,
+    });
+
+    Ok(())
+}
+
+pub fn set_creator_fee(ctx: Context<SetParams>, creator_fee_basis_points: u64) -> Result<()> {
+    let global = &mut ctx.accounts.global;
+
+    //confirm program is initialized
+    require!(
+        global.initialized,
+        CurveLaunchpadError::NotInitialized
+    );
+
+    //confirm user is the authority
+    require!(
+        global.authority == *ctx.accounts.user.to_account_info().key,
+        CurveLaunchpadError::InvalidAuthority
+    );
+
+    //these params can only change through queue_params once the timelock is enabled
+    require!(
+        global.params_timelock_seconds == 0,
+        CurveLaunchpadError::TimelockEnabled
+    );
+
+    validate_creator_fee(creator_fee_basis_points)?;
+
+    global.creator_fee_basis_points = creator_fee_basis_points;
+
+    emit_cpi!(SetCreatorFeeEvent {
+        creator_fee_basis_points,
+    });
+
+    Ok(())
+}
+
+pub fn set_fee_schedule(ctx: Context<SetParams>, fee_schedule: FeeSchedule) -> Result<()> {
+    let global = &mut ctx.accounts.global;
+
+    //confirm program is initialized
+    require!(
+        global.initialized,
+        CurveLaunchpadError::NotInitialized
+    );
+
+    //confirm user is the authority
+    require!(
+        global.authority == *ctx.accounts.user.to_account_info().key,
+        CurveLaunchpadError::InvalidAuthority
+    );
+
+    //the fee schedule can only change through queue_params once the timelock is enabled
+    require!(
+        global.params_timelock_seconds == 0,
+        CurveLaunchpadError::TimelockEnabled
+    );
+
+    require!(
+        fee_schedule.is_valid(),
+        CurveLaunchpadError::InvalidFeeSchedule
+    );
+
+    //only applies to curves created from now on
+    global.fee_schedule = fee_schedule;
+
+    emit_cpi!(SetFeeScheduleEvent {
+        fee_schedule,
+    });
+
+    Ok(())
+}
+
+pub fn set_decimals_range(ctx: Context<SetParams>, min_decimals: u8, max_decimals: u8) -> Result<()> {
+    let global = &mut ctx.accounts.global;
+
+    //confirm program is initialized
+    require!(
+        global.initialized,
+        CurveLaunchpadError::NotInitialized
+    );
+
+    //confirm user is the authority
+    require!(
+        global.authority == *ctx.accounts.user.to_account_info().key,
+        CurveLaunchpadError::InvalidAuthority
+    );
+
+    //these params can only change through queue_params once the timelock is enabled
+    require!(
+        global.params_timelock_seconds == 0,
+        CurveLaunchpadError::TimelockEnabled
+    );
+
+    validate_decimals_range(min_decimals, max_decimals)?;
+
+    global.min_decimals = min_decimals;
+    global.max_decimals = max_decimals;
+
+    emit_cpi!(SetDecimalsRangeEvent {
+        min_decimals,
+        max_decimals,
+    });
+
+    Ok(())
+}
+
+//proposing the default pubkey cancels a pending transfer
+pub fn propose_authority(ctx: Context<SetParams>, new_authority: Pubkey) -> Result<()> {
+    let global = &mut ctx.accounts.global;
+
+    //confirm program is initialized
+    require!(
+        global.initialized,
+        CurveLaunchpadError::NotInitialized
+    );
+
+    //confirm user is the authority
+    require!(
+        global.authority == *ctx.accounts.user.to_account_info().key,
+        CurveLaunchpadError::InvalidAuthority
+    );
+
+    global.pending_authority = new_authority;
+
+    emit_cpi!(ProposeAuthorityEvent {
+        authority: global.authority,
+        pending_authority: new_authority,
+    });
+
+    Ok(())
+}
+
+pub fn accept_authority(ctx: Context<SetParams>) -> Result<()> {
+    let global = &mut ctx.accounts.global;
+
+    //confirm program is initialized
+    require!(
+        global.initialized,
+        CurveLaunchpadError::NotInitialized
+    );
+
+    //confirm user is the proposed authority
+    require!(
+        global.pending_authority != Pubkey::default()
+            && global.pending_authority == *ctx.accounts.user.to_account_info().key,
+        CurveLaunchpadError::InvalidAuthority
+    );
+
+    let old_authority = global.authority;
+    global.authority = global.pending_authority;
+    global.pending_authority = Pubkey::default();
+
+    emit_cpi!(AcceptAuthorityEvent {
+        old_authority,
+        new_authority: global.authority,
+    });
+
+    Ok(())
+}
+
+pub fn set_fee_config(
+    ctx: Context<SetParams>,
+    fee_recipient: Pubkey,
+    fee_basis_points: u64,
+) -> Result<()> {
+    let global = &mut ctx.accounts.global;
+
+    //confirm program is initialized
+    require!(
+        global.initialized,
+        CurveLaunchpadError::NotInitialized
+    );
+
+    //confirm user is the authority
+    require!(
+        global.authority == *ctx.accounts.user.to_account_info().key,
+        CurveLaunchpadError::InvalidAuthority
+    );
+
+    //these params can only change through queue_params once the timelock is enabled
+    require!(
+        global.params_timelock_seconds == 0,
+        CurveLaunchpadError::TimelockEnabled
+    );
+
+    validate_fee_config(&fee_recipient, fee_basis_points)?;
+
+    global.fee_recipient = fee_recipient;
+    global.fee_basis_points = fee_basis_points;
+
+    emit_cpi!(SetFeeConfigEvent {
+        fee_recipient,
+        fee_basis_points,
+    });
+
+    Ok(())
+}
+
+pub fn set_curve_defaults(ctx: Context<SetParams>, curve_params: CurveParams) -> Result<()> {
+    let global = &mut ctx.accounts.global;
+
+    //confirm program is initialized
+    require!(
+        global.initialized,
+        CurveLaunchpadError::NotInitialized
+    );
+
+    //confirm user is the authority
+    require!(
+        global.authority == *ctx.accounts.user.to_account_info().key,
+        CurveLaunchpadError::InvalidAuthority
+    );
+
+    //these params can only change through queue_params once the timelock is enabled
+    require!(
+        global.params_timelock_seconds == 0,
+        CurveLaunchpadError::TimelockEnabled
+    );
+
+    validate_curve_defaults(&curve_params)?;
+
+    //only applies to curves created from now on
+    global.initial_virtual_token_reserves = curve_params.initial_virtual_token_reserves;
+    global.initial_virtual_sol_reserves = curve_params.initial_virtual_sol_reserves;
+    global.initial_real_token_reserves = curve_params.initial_real_token_reserves;
+    global.initial_token_supply = curve_params.initial_token_supply;
+
+    emit_cpi!(SetCurveDefaultsEvent {
+        curve_params,
+    });
+
+    Ok(())
+}
+
+pub fn set_withdraw_authority(ctx: Context<SetParams>, withdraw_authority: Pubkey) -> Result<()> {
+    let global = &mut ctx.accounts.global;
+
+    //confirm program is initialized
+    require!(
+        global.initialized,
+        CurveLaunchpadError::NotInitialized
+    );
+
+    //confirm user is the authority
+    require!(
+        global.authority == *ctx.accounts.user.to_account_info().key,
+        CurveLaunchpadError::InvalidAuthority
+    );
+
+    //these params can only change through queue_params once the timelock is enabled
+    require!(
+        global.params_timelock_seconds == 0,
+        CurveLaunchpadError::TimelockEnabled
+    );
+
+    validate_withdraw_authority(&withdraw_authority)?;
+
+    let old_withdraw_authority = global.withdraw_authority;
+    global.withdraw_authority = withdraw_authority;
+
+    emit_cpi!(SetWithdrawAuthorityEvent {
+        old_withdraw_authority,
+        withdraw_authority,
+    });
+
+    Ok(())
+}
+
+pub fn set_pauser(ctx: Context<SetParams>, pauser: Pubkey) -> Result<()> {
+    let global = &mut ctx.accounts.global;
+
+    //confirm program is initialized
+    require!(
+        global.initialized,
+        CurveLaunchpadError::NotInitialized
+    );
+
+    //confirm user is the authority
+    require!(
+        global.authority == *ctx.accounts.user.to_account_info().key,
+        CurveLaunchpadError::InvalidAuthority
+    );
+
+    global.pauser = pauser;
+
+    emit_cpi!(SetPauserEvent {
+        pauser,
+    });
+
+    Ok(())
+}
+
+//the timelock can only be lengthened, shortening it would bypass it
+pub fn set_params_timelock(ctx: Context<SetParams>, params_timelock_seconds: i64) -> Result<()> {
+    let global = &mut ctx.accounts.global;
+
+    //confirm program is initialized
+    require!(
+        global.initialized,
+        CurveLaunchpadError::NotInitialized
+    );
+
+    //confirm user is the authority
+    require!(
+        global.authority == *ctx.accounts.user.to_account_info().key,
+        CurveLaunchpadError::InvalidAuthority
+    );
+
+    require!(
+        params_timelock_seconds >= global.params_timelock_seconds
+            && params_timelock_seconds <= MAX_PARAMS_TIMELOCK_SECONDS,
+        CurveLaunchpadError::InvalidTimelock
+    );
+
+    global.params_timelock_seconds = params_timelock_seconds;
+
+    emit_cpi!(SetParamsTimelockEvent {
+        params_timelock_seconds,
+    });
+
+    Ok(())
+}
+
+//usage tracked by the current window is kept, so lowering the cap applies immediately
+pub fn set_withdraw_limits(
+    ctx: Context<SetParams>,
+    min_interval_seconds: i64,
+    window_seconds: i64,
+    window_sol_cap: u64,
+) -> Result<()> {
+    let global = &mut ctx.accounts.global;
+
+    //confirm program is initialized
+    require!(
+        global.initialized,
+        CurveLaunchpadError::NotInitialized
+    );
+
+    //confirm user is the authority
+    require!(
+        global.authority == *ctx.accounts.user.to_account_info().key,
+        CurveLaunchpadError::InvalidAuthority
+    );
+
+    //these params can only change through queue_params once the timelock is enabled
+    require!(
+        global.params_timelock_seconds == 0,
+        CurveLaunchpadError::TimelockEnabled
+    );
+
+    let withdraw_limits = WithdrawLimits {
+        min_interval_seconds,
+        window_seconds,
+        window_sol_cap,
+        ..global.withdraw_limits
+    };
+
+    validate_withdraw_limits(&withdraw_limits)?;
+
+    global.withdraw_limits = withdraw_limits;
+
+    emit_cpi!(SetWithdrawLimitsEvent {
+        min_interval_seconds,
+        window_seconds,
+        window_sol_cap,
+    });
+
+    Ok(())
+}
+
+pub fn set_withdraw_destinations(
+    ctx: Context<SetParams>,
+    withdraw_destinations: WithdrawDestinations,
+) -> Result<()> {
+    let global = &mut ctx.accounts.global;
+
+    //confirm program is initialized
+    require!(
+        global.initialized,
+        CurveLaunchpadError::NotInitialized
+    );
+
+    //confirm user is the authority
+    require!(
+        global.authority == *ctx.accounts.user.to_account_info().key,
+        CurveLaunchpadError::InvalidAuthority
+    );
+
+    //these params can only change through queue_params once the timelock is enabled
+    require!(
+        global.params_timelock_seconds == 0,
+        CurveLaunchpadError::TimelockEnabled
+    );
+
+    validate_withdraw_destinations(&withdraw_destinations)?;
+
+    global.withdraw_destinations = withdraw_destinations;
+
+    emit_cpi!(SetWithdrawDestinationsEvent {
+        withdraw_destinations,
+    });
+
+    Ok(())
+}
+
+//a fee above 100% makes sell's sol_amount - fee underflow
+pub(crate) fn validate_fee_config(fee_recipient: &Pubkey, fee_basis_points: u64) -> Result<()> {
+    require!(
+        *fee_recipient != Pubkey::default(),
+        CurveLaunchpadError::InvalidFeeRecipient
+    );
+
+    require!(
+        fee_basis_points <= 10000,
+        CurveLaunchpadError::InvalidFeeBasisPoints
+    );
+
+    Ok(())
+}
+
+//virtual token reserves >= real token reserves and supply >= real token reserves
+pub(crate) fn validate_curve_defaults(curve_params: &CurveParams) -> Result<()> {
+    require!(
+        curve_params.is_valid(),
+        CurveLaunchpadError::InvalidCurveParams
+    );
+
+    Ok(())
+}
+
+pub(crate) fn validate_withdraw_authority(withdraw_authority: &Pubkey) -> Result<()> {
+    require!(
+        *withdraw_authority != Pubkey::default(),
+        CurveLaunchpadError::InvalidWithdrawAuthority
+    );
+
+    Ok(())
+}
+
+//an empty mask would make every create fail
+pub(crate) fn validate_allowed_curve_kinds(allowed_curve_kinds: u8) -> Result<()> {
+    require!(
+        CurveKind::is_valid_mask(allowed_curve_kinds),
+        CurveLaunchpadError::InvalidCurveKinds
+    );
+
+    Ok(())
+}
+
+//confirm the bounds describe a non-empty range
+pub(crate) fn validate_curve_param_bounds(
+    min_curve_params: &CurveParams,
+    max_curve_params: &CurveParams,
+) -> Result<()> {
+    require!(
+        min_curve_params.is_within(min_curve_params, max_curve_params),
+        CurveLaunchpadError::InvalidCurveParams
+    );
+
+    Ok(())
+}
+
+//the creator share can't exceed the whole fee
+pub(crate) fn validate_creator_fee(creator_fee_basis_points: u64) -> Result<()> {
+    require!(
+        creator_fee_basis_points <= 10000,
+        CurveLaunchpadError::InvalidFeeBasisPoints
+    );
+
+    Ok(())
+}
+
+pub(crate) fn validate_decimals_range(min_decimals: u8, max_decimals: u8) -> Result<()> {
+    require!(
+        min_decimals <= max_decimals,
+        CurveLaunchpadError::InvalidDecimals
+    );
+
+    Ok(())
+}
+
+pub(crate) fn validate_withdraw_limits(withdraw_limits: &WithdrawLimits) -> Result<()> {
+    require!(
+        withdraw_limits.is_valid(),
+        CurveLaunchpadError::InvalidWithdrawLimits
+    );
+
+    Ok(())
+}
+
+pub(crate) fn validate_withdraw_destinations(withdraw_destinations: &WithdrawDestinations) -> Result<()> {
+    require!(
+        withdraw_destinations.is_valid(),
+        CurveLaunchpadError::InvalidWithdrawDestinations
+    );
+
+    Ok(())
+}
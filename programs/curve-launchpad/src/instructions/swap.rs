@@ -0,0 +1,349 @@
+use anchor_lang::{prelude::*, solana_program::system_instruction};
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::{
+    amm::PoolAMM,
+    calculate_fee, get_fee_split,
+    state::{CreatorVault, Global, Pool},
+    CurveLaunchpadError, TradeEvent, TradeFeeEvent,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct Swap<'info> {
+    #[account(mut)]
+    user: Signer<'info>,
+
+    #[account(
+        seeds = [Global::SEED_PREFIX],
+        bump,
+    )]
+    global: Box<Account<'info, Global>>,
+
+    /// CHECK: Using global state to validate fee_recipient account
+    #[account(mut)]
+    fee_recipient: AccountInfo<'info>,
+
+    mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [Pool::SEED_PREFIX, mint.to_account_info().key.as_ref()],
+        bump,
+    )]
+    pool: Box<Account<'info, Pool>>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = pool,
+        associated_token::token_program = token_program,
+    )]
+    pool_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: Using seed to validate creator_vault account, pools of curves without a creator never pay it
+    #[account(
+        mut,
+        seeds = [CreatorVault::SEED_PREFIX, pool.creator.as_ref()],
+        bump,
+    )]
+    creator_vault: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = user,
+        associated_token::token_program = token_program,
+    )]
+    user_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    system_program: Program<'info, System>,
+
+    token_program: Interface<'info, TokenInterface>,
+}
+
+//amount_in is fixed, amount_out must be at least minimum_amount_out
+pub fn swap_base_in(
+    ctx: Context<Swap>,
+    amount_in: u64,
+    minimum_amount_out: u64,
+    is_buy: bool,
+) -> Result<()> {
+    let pool_amm = get_pool_amm(&ctx.accounts.pool);
+
+    if is_buy {
+        //amount_in includes the fee, only the remainder is swapped
+        let sol_amount_before_fee = ctx.accounts.pool.get_buy_amount_before_fee(amount_in);
+        require!(sol_amount_before_fee > 0, CurveLaunchpadError::MinBuy);
+
+        let token_amount = pool_amm
+            .get_tokens_for_sol(sol_amount_before_fee as u128)
+            .ok_or(CurveLaunchpadError::CurveMathOverflow)? as u64;
+
+        require!(
+            token_amount >= minimum_amount_out,
+            CurveLaunchpadError::MinTokenOutputExceeded,
+        );
+
+        swap_buy(ctx, token_amount, amount_in)
+    } else {
+        swap_sell(ctx, amount_in, minimum_amount_out)
+    }
+}
+
+//amount_out is fixed, amount_in must be at most maximum_amount_in
+pub fn swap_base_out(
+    ctx: Context<Swap>,
+    amount_out: u64,
+    maximum_amount_in: u64,
+    is_buy: bool,
+) -> Result<()> {
+    let pool_amm = get_pool_amm(&ctx.accounts.pool);
+
+    if is_buy {
+        swap_buy(ctx, amount_out, maximum_amount_in)
+    } else {
+        //confirm pool has enough SOL to pay out amount_out after the fee
+        let sol_amount_before_fee = ctx
+            .accounts
+            .pool
+            .get_sell_amount_before_fee(amount_out)
+            .ok_or(CurveLaunchpadError::InsufficientSOL)?;
+        let token_amount = pool_amm
+            .get_tokens_for_sell_sol(sol_amount_before_fee as u128, 0)
+            .ok_or(CurveLaunchpadError::InsufficientSOL)? as u64;
+
+        require!(
+            token_amount <= maximum_amount_in,
+            CurveLaunchpadError::MaxTokenInputExceeded,
+        );
+
+        swap_sell(ctx, token_amount, amount_out)
+    }
+}
+
+fn get_pool_amm(pool: &Pool) -> PoolAMM {
+    PoolAMM::new(
+        pool.sol_reserves as u128,
+        pool.token_reserves as u128,
+        pool.lp_supply as u128,
+    )
+}
+
+fn validate_swap(ctx: &Context<Swap>) -> Result<()> {
+    require!(
+        ctx.accounts.global.initialized,
+        CurveLaunchpadError::NotInitialized
+    );
+
+    //invalid fee recipient
+    require!(
+        ctx.accounts.fee_recipient.key == &ctx.accounts.global.fee_recipient,
+        CurveLaunchpadError::InvalidFeeRecipient,
+    );
+
+    Ok(())
+}
+
+fn swap_buy(ctx: Context<Swap>, token_amount: u64, max_sol_cost: u64) -> Result<()> {
+    validate_swap(&ctx)?;
+
+    require!(token_amount > 0, CurveLaunchpadError::MinBuy);
+
+    let mut pool_amm = get_pool_amm(&ctx.accounts.pool);
+
+    let buy_result = pool_amm
+        .apply_buy(token_amount as u128)
+        .ok_or(CurveLaunchpadError::InsufficientTokens)?;
+    let fee_basis_points = ctx.accounts.pool.get_fee_basis_points(buy_result.sol_amount);
+    let fee = calculate_fee(buy_result.sol_amount, fee_basis_points);
+    let buy_amount_with_fee = buy_result.sol_amount + fee;
+
+    //check if the amount of SOL to transfer plus fee is less than the max_sol_cost
+    require!(
+        buy_amount_with_fee <= max_sol_cost,
+        CurveLaunchpadError::MaxSOLCostExceeded,
+    );
+
+    //check if the user has enough SOL
+    require!(
+        ctx.accounts.user.lamports() >= buy_amount_with_fee,
+        CurveLaunchpadError::InsufficientSOL,
+    );
+
+    let (protocol_fee, creator_fee) = get_fee_split(
+        &ctx.accounts.pool.creator,
+        ctx.accounts.pool.creator_fee_basis_points,
+        fee,
+    );
+
+    //transfer SOL to pool, fee recipient and creator vault
+    for (to_account, amount) in [
+        (ctx.accounts.pool.to_account_info(), buy_result.sol_amount),
+        (ctx.accounts.fee_recipient.to_account_info(), protocol_fee),
+        (ctx.accounts.creator_vault.to_account_info(), creator_fee),
+    ] {
+        if amount == 0 {
+            continue;
+        }
+
+        let transfer_instruction = system_instruction::transfer(
+            ctx.accounts.user.key,
+            to_account.key,
+            amount,
+        );
+
+        anchor_lang::solana_program::program::invoke(
+            &transfer_instruction,
+            &[
+                ctx.accounts.user.to_account_info(),
+                to_account,
+                ctx.accounts.system_program.to_account_info(),
+            ],
+        )?;
+    }
+
+    //transfer SPL
+    let cpi_accounts = TransferChecked {
+        from: ctx.accounts.pool_token_account.to_account_info().clone(),
+        mint: ctx.accounts.mint.to_account_info(),
+        to: ctx.accounts.user_token_account.to_account_info().clone(),
+        authority: ctx.accounts.pool.to_account_info().clone(),
+    };
+
+    let signer: [&[&[u8]]; 1] = [&[
+        Pool::SEED_PREFIX,
+        ctx.accounts.mint.to_account_info().key.as_ref(),
+        &[ctx.bumps.pool],
+    ]];
+
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+            &signer,
+        ),
+        buy_result.token_amount,
+        ctx.accounts.mint.decimals,
+    )?;
+
+    let pool = &mut ctx.accounts.pool;
+    pool.sol_reserves = pool_amm.sol_reserves as u64;
+    pool.token_reserves = pool_amm.token_reserves as u64;
+
+    emit_trade_event(&ctx, buy_result.sol_amount, buy_result.token_amount, true)?;
+    emit_trade_fee_event(&ctx, fee, protocol_fee, creator_fee, true)?;
+
+    Ok(())
+}
+
+fn swap_sell(ctx: Context<Swap>, token_amount: u64, min_sol_output: u64) -> Result<()> {
+    validate_swap(&ctx)?;
+
+    require!(token_amount > 0, CurveLaunchpadError::MinSell);
+
+    //confirm user has enough tokens
+    require!(
+        ctx.accounts.user_token_account.amount >= token_amount,
+        CurveLaunchpadError::InsufficientTokens,
+    );
+
+    let mut pool_amm = get_pool_amm(&ctx.accounts.pool);
+
+    let sell_result = pool_amm
+        .apply_sell(token_amount as u128)
+        .ok_or(CurveLaunchpadError::InsufficientSOL)?;
+    let fee_basis_points = ctx.accounts.pool.get_fee_basis_points(sell_result.sol_amount);
+    let fee = calculate_fee(sell_result.sol_amount, fee_basis_points);
+
+    //the fee is subtracted from the sol amount to confirm the user minimum sol output is met
+    let sell_amount_minus_fee = sell_result.sol_amount - fee;
+
+    //confirm min sol output is greater than sol output
+    require!(
+        sell_amount_minus_fee >= min_sol_output,
+        CurveLaunchpadError::MinSOLOutputExceeded,
+    );
+
+    //transfer SPL
+    let cpi_accounts = TransferChecked {
+        from: ctx.accounts.user_token_account.to_account_info().clone(),
+        mint: ctx.accounts.mint.to_account_info(),
+        to: ctx.accounts.pool_token_account.to_account_info().clone(),
+        authority: ctx.accounts.user.to_account_info().clone(),
+    };
+
+    token_interface::transfer_checked(
+        CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts),
+        sell_result.token_amount,
+        ctx.accounts.mint.decimals,
+    )?;
+
+    //transfer SOL to user, fee recipient and creator vault, the pool pays out sol_amount in total
+    let (protocol_fee, creator_fee) = get_fee_split(
+        &ctx.accounts.pool.creator,
+        ctx.accounts.pool.creator_fee_basis_points,
+        fee,
+    );
+    let from_account = &ctx.accounts.pool;
+
+    **from_account.to_account_info().try_borrow_mut_lamports()? -= sell_result.sol_amount;
+    **ctx.accounts.user.try_borrow_mut_lamports()? += sell_amount_minus_fee;
+    **ctx.accounts.fee_recipient.try_borrow_mut_lamports()? += protocol_fee;
+    **ctx.accounts.creator_vault.try_borrow_mut_lamports()? += creator_fee;
+
+    let pool = &mut ctx.accounts.pool;
+    pool.sol_reserves = pool_amm.sol_reserves as u64;
+    pool.token_reserves = pool_amm.token_reserves as u64;
+
+    emit_trade_event(&ctx, sell_result.sol_amount, sell_result.token_amount, false)?;
+    emit_trade_fee_event(&ctx, fee, protocol_fee, creator_fee, false)?;
+
+    Ok(())
+}
+
+//pool swaps reuse TradeEvent, reporting the pool reserves as both virtual and real reserves
+fn emit_trade_event(
+    ctx: &Context<Swap>,
+    sol_amount: u64,
+    token_amount: u64,
+    is_buy: bool,
+) -> Result<()> {
+    let pool = &ctx.accounts.pool;
+
+    emit_cpi!(TradeEvent {
+        mint: *ctx.accounts.mint.to_account_info().key,
+        sol_amount,
+        token_amount,
+        is_buy,
+        user: *ctx.accounts.user.to_account_info().key,
+        timestamp: Clock::get()?.unix_timestamp,
+        virtual_sol_reserves: pool.sol_reserves,
+        virtual_token_reserves: pool.token_reserves,
+        real_sol_reserves: pool.sol_reserves,
+        real_token_reserves: pool.token_reserves,
+    });
+
+    Ok(())
+}
+
+fn emit_trade_fee_event(
+    ctx: &Context<Swap>,
+    fee: u64,
+    protocol_fee: u64,
+    creator_fee: u64,
+    is_buy: bool,
+) -> Result<()> {
+    emit_cpi!(TradeFeeEvent {
+        mint: *ctx.accounts.mint.to_account_info().key,
+        user: *ctx.accounts.user.to_account_info().key,
+        creator: ctx.accounts.pool.creator,
+        is_buy,
+        fee,
+        protocol_fee,
+        creator_fee,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,187 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    state::{Global, ParamsUpdate, PendingParams, WithdrawLimits},
+    validate_allowed_curve_kinds, validate_creator_fee, validate_curve_defaults,
+    validate_curve_param_bounds, validate_decimals_range, validate_fee_config,
+    validate_withdraw_authority, validate_withdraw_destinations, validate_withdraw_limits,
+    CancelParamsEvent, CurveLaunchpadError, ExecuteParamsEvent, QueueParamsEvent,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct QueueParams<'info> {
+    #[account(
+        seeds = [Global::SEED_PREFIX],
+        bump,
+    )]
+    global: Box<Account<'info, Global>>,
+
+    #[account(mut)]
+    user: Signer<'info>,
+
+    //only one change can be pending at a time, cancel it to queue another
+    #[account(
+        init,
+        payer = user,
+        seeds = [PendingParams::SEED_PREFIX],
+        bump,
+        space = 8 + PendingParams::INIT_SPACE,
+    )]
+    pending_params: Box<Account<'info, PendingParams>>,
+
+    system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ExecuteParams<'info> {
+    #[account(
+        mut,
+        seeds = [Global::SEED_PREFIX],
+        bump,
+    )]
+    global: Box<Account<'info, Global>>,
+
+    #[account(mut)]
+    user: Signer<'info>,
+
+    #[account(
+        mut,
+        close = user,
+        seeds = [PendingParams::SEED_PREFIX],
+        bump,
+    )]
+    pending_params: Box<Account<'info, PendingParams>>,
+}
+
+pub fn queue_params(ctx: Context<QueueParams>, params: ParamsUpdate) -> Result<()> {
+    let global = &ctx.accounts.global;
+
+    //confirm program is initialized
+    require!(
+        global.initialized,
+        CurveLaunchpadError::NotInitialized
+    );
+
+    //confirm user is the authority
+    require!(
+        global.authority == *ctx.accounts.user.to_account_info().key,
+        CurveLaunchpadError::InvalidAuthority
+    );
+
+    //reject now rather than after the delay
+    validate_fee_config(&params.fee_recipient, params.fee_basis_points)?;
+    validate_withdraw_authority(&params.withdraw_authority)?;
+    validate_curve_defaults(&params.curve_params)?;
+
+    require!(
+        params.fee_schedule.is_valid(),
+        CurveLaunchpadError::InvalidFeeSchedule
+    );
+
+    validate_creator_fee(params.creator_fee_basis_points)?;
+    validate_allowed_curve_kinds(params.allowed_curve_kinds)?;
+    validate_curve_param_bounds(&params.min_curve_params, &params.max_curve_params)?;
+    validate_decimals_range(params.min_decimals, params.max_decimals)?;
+    validate_withdraw_limits(&WithdrawLimits {
+        min_interval_seconds: params.withdraw_min_interval_seconds,
+        window_seconds: params.withdraw_window_seconds,
+        window_sol_cap: params.withdraw_window_sol_cap,
+        ..global.withdraw_limits
+    })?;
+    validate_withdraw_destinations(&params.withdraw_destinations)?;
+
+    let queued_at = Clock::get()?.unix_timestamp;
+    let execute_after = queued_at
+        .checked_add(global.params_timelock_seconds)
+        .ok_or(CurveLaunchpadError::InvalidTimelock)?;
+
+    let pending_params = &mut ctx.accounts.pending_params;
+    pending_params.params = params;
+    pending_params.queued_at = queued_at;
+    pending_params.execute_after = execute_after;
+
+    emit_cpi!(QueueParamsEvent {
+        params,
+        queued_at,
+        execute_after,
+    });
+
+    Ok(())
+}
+
+pub fn execute_params(ctx: Context<ExecuteParams>) -> Result<()> {
+    //confirm program is initialized
+    require!(
+        ctx.accounts.global.initialized,
+        CurveLaunchpadError::NotInitialized
+    );
+
+    //confirm user is the authority
+    require!(
+        ctx.accounts.global.authority == *ctx.accounts.user.to_account_info().key,
+        CurveLaunchpadError::InvalidAuthority
+    );
+
+    let timestamp = Clock::get()?.unix_timestamp;
+    require!(
+        ctx.accounts.pending_params.is_executable(timestamp),
+        CurveLaunchpadError::TimelockNotElapsed
+    );
+
+    let params = ctx.accounts.pending_params.params;
+
+    let global = &mut ctx.accounts.global;
+    global.fee_recipient = params.fee_recipient;
+    global.withdraw_authority = params.withdraw_authority;
+    global.initial_virtual_token_reserves = params.curve_params.initial_virtual_token_reserves;
+    global.initial_virtual_sol_reserves = params.curve_params.initial_virtual_sol_reserves;
+    global.initial_real_token_reserves = params.curve_params.initial_real_token_reserves;
+    global.initial_token_supply = params.curve_params.initial_token_supply;
+    global.fee_basis_points = params.fee_basis_points;
+    global.fee_schedule = params.fee_schedule;
+    global.creator_fee_basis_points = params.creator_fee_basis_points;
+    global.allowed_curve_kinds = params.allowed_curve_kinds;
+    global.min_curve_params = params.min_curve_params;
+    global.max_curve_params = params.max_curve_params;
+    global.min_decimals = params.min_decimals;
+    global.max_decimals = params.max_decimals;
+    global.withdraw_destinations = params.withdraw_destinations;
+
+    //usage tracked by the current window is kept, like set_withdraw_limits
+    global.withdraw_limits = WithdrawLimits {
+        min_interval_seconds: params.withdraw_min_interval_seconds,
+        window_seconds: params.withdraw_window_seconds,
+        window_sol_cap: params.withdraw_window_sol_cap,
+        ..global.withdraw_limits
+    };
+
+    emit_cpi!(ExecuteParamsEvent {
+        params,
+        timestamp,
+    });
+
+    Ok(())
+}
+
+pub fn cancel_params(ctx: Context<ExecuteParams>) -> Result<()> {
+    //confirm program is initialized
+    require!(
+        ctx.accounts.global.initialized,
+        CurveLaunchpadError::NotInitialized
+    );
+
+    //confirm user is the authority
+    require!(
+        ctx.accounts.global.authority == *ctx.accounts.user.to_account_info().key,
+        CurveLaunchpadError::InvalidAuthority
+    );
+
+    emit_cpi!(CancelParamsEvent {
+        params: ctx.accounts.pending_params.params,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
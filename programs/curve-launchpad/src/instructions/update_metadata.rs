@@ -0,0 +1,168 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    metadata::{
+        mpl_token_metadata::types::DataV2, update_metadata_accounts_v2, MetadataAccount,
+        Metadata as Metaplex, UpdateMetadataAccountsV2,
+    },
+    token::Mint,
+};
+
+use crate::{state::BondingCurve, CurveLaunchpadError, MetadataUpdatedEvent};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateMetadata<'info> {
+    user: Signer<'info>,
+
+    mint: Account<'info, Mint>,
+
+    /// CHECK: Using seed to validate mint_authority account
+    #[account(
+        seeds=[b"mint-authority"],
+        bump,
+    )]
+    mint_authority: AccountInfo<'info>,
+
+    #[account(
+        seeds = [BondingCurve::SEED_PREFIX, mint.to_account_info().key.as_ref()],
+        bump,
+    )]
+    bonding_curve: Box<Account<'info, BondingCurve>>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"metadata",
+            token_metadata_program.key.as_ref(),
+            mint.to_account_info().key.as_ref()
+        ],
+        seeds::program = token_metadata_program.key(),
+        bump,
+    )]
+    metadata: Box<Account<'info, MetadataAccount>>,
+
+    token_metadata_program: Program<'info, Metaplex>,
+}
+
+//name and symbol left as None keep their current value
+pub fn update_metadata(
+    ctx: Context<UpdateMetadata>,
+    name: Option<String>,
+    symbol: Option<String>,
+    uri: String,
+) -> Result<()> {
+    //only the creator can update the metadata
+    require!(
+        ctx.accounts.user.key() == ctx.accounts.bonding_curve.creator,
+        CurveLaunchpadError::InvalidCreator,
+    );
+
+    //metadata can only change while the curve is live
+    require!(
+        !ctx.accounts.bonding_curve.complete,
+        CurveLaunchpadError::BondingCurveComplete,
+    );
+
+    //metaplex pads stored strings with null bytes
+    let metadata = &ctx.accounts.metadata;
+    let name = name.unwrap_or_else(|| metadata.name.trim_end_matches('\0').to_string());
+    let symbol = symbol.unwrap_or_else(|| metadata.symbol.trim_end_matches('\0').to_string());
+
+    let token_data = DataV2 {
+        name: name.clone(),
+        symbol: symbol.clone(),
+        uri: uri.clone(),
+        seller_fee_basis_points: 0,
+        creators: None,
+        collection: None,
+        uses: None,
+    };
+
+    set_metadata(&ctx, token_data)?;
+
+    emit_cpi!(MetadataUpdatedEvent {
+        mint: *ctx.accounts.mint.to_account_info().key,
+        name,
+        symbol,
+        uri,
+        is_mutable: true,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+//anyone can freeze the metadata once the curve completes, migrate freezes it as well
+pub fn freeze_metadata(ctx: Context<UpdateMetadata>) -> Result<()> {
+    require!(
+        ctx.accounts.bonding_curve.complete,
+        CurveLaunchpadError::BondingCurveNotComplete,
+    );
+
+    freeze_metaplex_metadata(
+        ctx.accounts.token_metadata_program.to_account_info(),
+        ctx.accounts.metadata.to_account_info(),
+        ctx.accounts.mint_authority.to_account_info(),
+        ctx.bumps.mint_authority,
+    )?;
+
+    let metadata = &ctx.accounts.metadata;
+
+    emit_cpi!(MetadataUpdatedEvent {
+        mint: *ctx.accounts.mint.to_account_info().key,
+        name: metadata.name.trim_end_matches('\0').to_string(),
+        symbol: metadata.symbol.trim_end_matches('\0').to_string(),
+        uri: metadata.uri.trim_end_matches('\0').to_string(),
+        is_mutable: false,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+fn set_metadata(ctx: &Context<UpdateMetadata>, data: DataV2) -> Result<()> {
+    let seeds = &["mint-authority".as_bytes(), &[ctx.bumps.mint_authority]];
+    let signer = [&seeds[..]];
+
+    update_metadata_accounts_v2(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_metadata_program.to_account_info(),
+            UpdateMetadataAccountsV2 {
+                metadata: ctx.accounts.metadata.to_account_info(),
+                update_authority: ctx.accounts.mint_authority.to_account_info(),
+            },
+            &signer,
+        ),
+        None,
+        Some(data),
+        None,
+        None,
+    )
+}
+
+//metaplex always needs an update authority, so it is handed to the default pubkey which can never sign,
+//the same as clearing it on a Token-2022 mint
+pub(crate) fn freeze_metaplex_metadata<'info>(
+    token_metadata_program: AccountInfo<'info>,
+    metadata: AccountInfo<'info>,
+    mint_authority: AccountInfo<'info>,
+    mint_authority_bump: u8,
+) -> Result<()> {
+    let seeds = &["mint-authority".as_bytes(), &[mint_authority_bump]];
+    let signer = [&seeds[..]];
+
+    update_metadata_accounts_v2(
+        CpiContext::new_with_signer(
+            token_metadata_program,
+            UpdateMetadataAccountsV2 {
+                metadata,
+                update_authority: mint_authority,
+            },
+            &signer,
+        ),
+        Some(Pubkey::default()),
+        None,
+        None,
+        Some(false),
+    )
+}
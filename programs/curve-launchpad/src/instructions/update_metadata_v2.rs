@@ -0,0 +1,201 @@
+use anchor_lang::{prelude::*, solana_program::system_instruction};
+use anchor_spl::{
+    token_2022::{
+        spl_token_2022::{
+            extension::{BaseStateWithExtensions, StateWithExtensions},
+            state::Mint as MintState,
+        },
+        Token2022,
+    },
+    token_2022_extensions::{
+        spl_pod::optional_keys::OptionalNonZeroPubkey,
+        spl_token_metadata_interface::state::{Field, TokenMetadata},
+        token_metadata_update_authority, token_metadata_update_field,
+        TokenMetadataUpdateAuthority, TokenMetadataUpdateField,
+    },
+    token_interface::Mint,
+};
+
+use crate::{state::BondingCurve, CurveLaunchpadError, MetadataUpdatedEvent};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateMetadataV2<'info> {
+    #[account(mut)]
+    user: Signer<'info>,
+
+    //the metadata lives on the mint itself, see create_v2
+    #[account(
+        mut,
+        mint::token_program = token_program,
+    )]
+    mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// CHECK: Using seed to validate mint_authority account
+    #[account(
+        seeds=[b"mint-authority"],
+        bump,
+    )]
+    mint_authority: AccountInfo<'info>,
+
+    #[account(
+        seeds = [BondingCurve::SEED_PREFIX, mint.to_account_info().key.as_ref()],
+        bump,
+    )]
+    bonding_curve: Box<Account<'info, BondingCurve>>,
+
+    system_program: Program<'info, System>,
+
+    token_program: Program<'info, Token2022>,
+}
+
+//name and symbol left as None keep their current value
+pub fn update_metadata_v2(
+    ctx: Context<UpdateMetadataV2>,
+    name: Option<String>,
+    symbol: Option<String>,
+    uri: String,
+) -> Result<()> {
+    //only the creator can update the metadata
+    require!(
+        ctx.accounts.user.key() == ctx.accounts.bonding_curve.creator,
+        CurveLaunchpadError::InvalidCreator,
+    );
+
+    //metadata can only change while the curve is live
+    require!(
+        !ctx.accounts.bonding_curve.complete,
+        CurveLaunchpadError::BondingCurveComplete,
+    );
+
+    let old_metadata = get_token_metadata(&ctx.accounts.mint.to_account_info())?;
+
+    let mut fields = vec![(Field::Uri, uri)];
+    if let Some(name) = name {
+        fields.push((Field::Name, name));
+    }
+    if let Some(symbol) = symbol {
+        fields.push((Field::Symbol, symbol));
+    }
+
+    let mut new_metadata = old_metadata.clone();
+    for (field, value) in fields.iter() {
+        new_metadata.update(field.clone(), value.clone());
+    }
+
+    //the token program reallocs the mint for longer values, the creator tops up the rent first
+    let mint_info = ctx.accounts.mint.to_account_info();
+    let new_size = (mint_info.data_len() + new_metadata.tlv_size_of()?)
+        .saturating_sub(old_metadata.tlv_size_of()?);
+    let lamports_needed = Rent::get()?
+        .minimum_balance(new_size)
+        .saturating_sub(mint_info.lamports());
+
+    if lamports_needed > 0 {
+        let transfer_instruction = system_instruction::transfer(
+            ctx.accounts.user.key,
+            mint_info.key,
+            lamports_needed,
+        );
+
+        anchor_lang::solana_program::program::invoke(
+            &transfer_instruction,
+            &[
+                ctx.accounts.user.to_account_info(),
+                mint_info.clone(),
+                ctx.accounts.system_program.to_account_info(),
+            ],
+        )?;
+    }
+
+    let seeds = &["mint-authority".as_bytes(), &[ctx.bumps.mint_authority]];
+    let signer = [&seeds[..]];
+
+    for (field, value) in fields {
+        token_metadata_update_field(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TokenMetadataUpdateField {
+                    token_program_id: ctx.accounts.token_program.to_account_info(),
+                    metadata: mint_info.clone(),
+                    update_authority: ctx.accounts.mint_authority.to_account_info(),
+                },
+                &signer,
+            ),
+            field,
+            value,
+        )?;
+    }
+
+    emit_cpi!(MetadataUpdatedEvent {
+        mint: *mint_info.key,
+        name: new_metadata.name,
+        symbol: new_metadata.symbol,
+        uri: new_metadata.uri,
+        is_mutable: true,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+//permissionless once the curve completes, migrate does the same for curves that graduate to a pool
+pub fn freeze_metadata_v2(ctx: Context<UpdateMetadataV2>) -> Result<()> {
+    require!(
+        ctx.accounts.bonding_curve.complete,
+        CurveLaunchpadError::BondingCurveNotComplete,
+    );
+
+    let metadata = get_token_metadata(&ctx.accounts.mint.to_account_info())?;
+
+    freeze_token_metadata(
+        ctx.accounts.token_program.to_account_info(),
+        ctx.accounts.mint.to_account_info(),
+        ctx.accounts.mint_authority.to_account_info(),
+        ctx.bumps.mint_authority,
+    )?;
+
+    emit_cpi!(MetadataUpdatedEvent {
+        mint: *ctx.accounts.mint.to_account_info().key,
+        name: metadata.name,
+        symbol: metadata.symbol,
+        uri: metadata.uri,
+        is_mutable: false,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+//clearing the update authority makes the metadata immutable
+pub(crate) fn freeze_token_metadata<'info>(
+    token_program: AccountInfo<'info>,
+    mint: AccountInfo<'info>,
+    mint_authority: AccountInfo<'info>,
+    mint_authority_bump: u8,
+) -> Result<()> {
+    let seeds = &["mint-authority".as_bytes(), &[mint_authority_bump]];
+    let signer = [&seeds[..]];
+
+    //new_authority is only read from the instruction data, the account is unused
+    token_metadata_update_authority(
+        CpiContext::new_with_signer(
+            token_program.clone(),
+            TokenMetadataUpdateAuthority {
+                token_program_id: token_program,
+                metadata: mint,
+                current_authority: mint_authority.clone(),
+                new_authority: mint_authority,
+            },
+            &signer,
+        ),
+        OptionalNonZeroPubkey::default(),
+    )
+}
+
+pub(crate) fn get_token_metadata(mint_info: &AccountInfo) -> Result<TokenMetadata> {
+    let data = mint_info.try_borrow_data()?;
+    let mint = StateWithExtensions::<MintState>::unpack(&data)?;
+
+    Ok(mint.get_variable_len_extension::<TokenMetadata>()?)
+}
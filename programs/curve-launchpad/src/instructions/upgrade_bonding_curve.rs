@@ -0,0 +1,115 @@
+use anchor_lang::{prelude::*, solana_program::system_instruction};
+use anchor_spl::token_interface::Mint;
+
+use crate::{
+    state::{BondingCurve, Global},
+    CurveLaunchpadError, UpgradeBondingCurveEvent,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpgradeBondingCurve<'info> {
+    #[account(mut)]
+    user: Signer<'info>,
+
+    #[account(
+        seeds = [Global::SEED_PREFIX],
+        bump,
+    )]
+    global: Box<Account<'info, Global>>,
+
+    mint: InterfaceAccount<'info, Mint>,
+
+    /// CHECK: Using seed and owner to validate bonding_curve account, the old layout can't be deserialized
+    #[account(
+        mut,
+        seeds = [BondingCurve::SEED_PREFIX, mint.to_account_info().key.as_ref()],
+        bump,
+        owner = crate::ID,
+    )]
+    bonding_curve: AccountInfo<'info>,
+
+    system_program: Program<'info, System>,
+}
+
+pub fn upgrade_bonding_curve(ctx: Context<UpgradeBondingCurve>) -> Result<()> {
+    require!(
+        ctx.accounts.global.initialized,
+        CurveLaunchpadError::NotInitialized
+    );
+
+    let bonding_curve_info = ctx.accounts.bonding_curve.to_account_info();
+    let old_size = bonding_curve_info.data_len();
+    let new_size = 8 + BondingCurve::INIT_SPACE;
+
+    require!(
+        old_size < new_size,
+        CurveLaunchpadError::BondingCurveAlreadyUpgraded
+    );
+
+    //user pays the rent for the extra space
+    let min_balance = Rent::get()?.minimum_balance(new_size);
+    let lamports_needed = min_balance.saturating_sub(bonding_curve_info.lamports());
+
+    if lamports_needed > 0 {
+        let transfer_instruction = system_instruction::transfer(
+            ctx.accounts.user.key,
+            bonding_curve_info.key,
+            lamports_needed,
+        );
+
+        anchor_lang::solana_program::program::invoke(
+            &transfer_instruction,
+            &[
+                ctx.accounts.user.to_account_info(),
+                bonding_curve_info.clone(),
+                ctx.accounts.system_program.to_account_info(),
+            ],
+        )?;
+    }
+
+    //new fields are appended, so zero filling leaves the old fields in place
+    bonding_curve_info.realloc(new_size, true)?;
+
+    let mut bonding_curve = {
+        let data = bonding_curve_info.try_borrow_data()?;
+        BondingCurve::try_deserialize(&mut &data[..])?
+    };
+
+    //accounts created before the snapshot fields existed priced trades off global
+    let global = &ctx.accounts.global;
+
+    if bonding_curve.initial_virtual_token_reserves == 0 {
+        bonding_curve.initial_virtual_token_reserves = global.initial_virtual_token_reserves;
+    }
+
+    if bonding_curve.initial_virtual_sol_reserves == 0 {
+        bonding_curve.initial_virtual_sol_reserves = global.initial_virtual_sol_reserves;
+    }
+
+    if bonding_curve.initial_real_token_reserves == 0 {
+        bonding_curve.initial_real_token_reserves = global.initial_real_token_reserves;
+    }
+
+    if bonding_curve.fee_basis_points == 0 {
+        bonding_curve.fee_basis_points = global.fee_basis_points;
+    }
+
+    if bonding_curve.creator_fee_basis_points == 0 {
+        bonding_curve.creator_fee_basis_points = global.creator_fee_basis_points;
+    }
+
+    {
+        let mut data = bonding_curve_info.try_borrow_mut_data()?;
+        bonding_curve.try_serialize(&mut &mut data[..])?;
+    }
+
+    emit_cpi!(UpgradeBondingCurveEvent {
+        mint: *ctx.accounts.mint.to_account_info().key,
+        bonding_curve: *bonding_curve_info.key,
+        old_size: old_size as u64,
+        new_size: new_size as u64,
+    });
+
+    Ok(())
+}
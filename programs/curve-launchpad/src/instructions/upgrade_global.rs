@@ -0,0 +1,117 @@
+use anchor_lang::{prelude::*, solana_program::system_instruction};
+
+use crate::{
+    amm::CurveKind, state::Global, CurveLaunchpadError, UpgradeGlobalEvent, DEFAULT_DECIMALS,
+    DEFAULT_PARAMS_TIMELOCK_SECONDS,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpgradeGlobal<'info> {
+    #[account(mut)]
+    user: Signer<'info>,
+
+    /// CHECK: Using seed and owner to validate global account, the old layout can't be deserialized
+    #[account(
+        mut,
+        seeds = [Global::SEED_PREFIX],
+        bump,
+        owner = crate::ID,
+    )]
+    global: AccountInfo<'info>,
+
+    system_program: Program<'info, System>,
+}
+
+pub fn upgrade_global(ctx: Context<UpgradeGlobal>) -> Result<()> {
+    let global_info = ctx.accounts.global.to_account_info();
+    let old_size = global_info.data_len();
+    let new_size = 8 + Global::INIT_SPACE;
+
+    require!(
+        old_size < new_size,
+        CurveLaunchpadError::GlobalAlreadyUpgraded
+    );
+
+    //authority is the first field after the discriminator in every layout
+    let authority = {
+        let data = global_info.try_borrow_data()?;
+        Pubkey::try_from(&data[8..40]).map_err(|_| CurveLaunchpadError::InvalidAuthority)?
+    };
+
+    require!(
+        ctx.accounts.user.key() == authority,
+        CurveLaunchpadError::InvalidAuthority
+    );
+
+    //authority pays the rent for the extra space
+    let min_balance = Rent::get()?.minimum_balance(new_size);
+    let lamports_needed = min_balance.saturating_sub(global_info.lamports());
+
+    if lamports_needed > 0 {
+        let transfer_instruction = system_instruction::transfer(
+            ctx.accounts.user.key,
+            global_info.key,
+            lamports_needed,
+        );
+
+        anchor_lang::solana_program::program::invoke(
+            &transfer_instruction,
+            &[
+                ctx.accounts.user.to_account_info(),
+                global_info.clone(),
+                ctx.accounts.system_program.to_account_info(),
+            ],
+        )?;
+    }
+
+    //new fields are appended, so zero filling leaves the old fields in place
+    global_info.realloc(new_size, true)?;
+
+    let mut global = {
+        let data = global_info.try_borrow_data()?;
+        Global::try_deserialize(&mut &data[..])?
+    };
+
+    //zeroed fields get the values initialize would have set, the rest default to disabled
+    if global.allowed_curve_kinds == 0 {
+        global.allowed_curve_kinds = CurveKind::ConstantProduct.mask();
+    }
+
+    if global.min_curve_params.initial_token_supply == 0 {
+        global.min_curve_params = global.default_curve_params();
+    }
+
+    if global.max_curve_params.initial_token_supply == 0 {
+        global.max_curve_params = global.default_curve_params();
+    }
+
+    if global.max_decimals == 0 {
+        global.min_decimals = DEFAULT_DECIMALS as u8;
+        global.max_decimals = DEFAULT_DECIMALS as u8;
+    }
+
+    //withdraws used to go to the withdraw authority itself
+    if global.withdraw_destinations.is_empty() && global.withdraw_authority != Pubkey::default() {
+        global.withdraw_destinations.count = 1;
+        global.withdraw_destinations.destinations[0] = global.withdraw_authority;
+    }
+
+    //params changes always go through queue_params
+    if global.params_timelock_seconds == 0 {
+        global.params_timelock_seconds = DEFAULT_PARAMS_TIMELOCK_SECONDS;
+    }
+
+    {
+        let mut data = global_info.try_borrow_mut_data()?;
+        global.try_serialize(&mut &mut data[..])?;
+    }
+
+    emit_cpi!(UpgradeGlobalEvent {
+        global: *global_info.key,
+        old_size: old_size as u64,
+        new_size: new_size as u64,
+    });
+
+    Ok(())
+}
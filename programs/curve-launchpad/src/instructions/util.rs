@@ -1,3 +1,4 @@
+use anchor_lang::prelude::*;
 
 pub fn calculate_fee(
     amount: u64,
@@ -6,6 +7,35 @@ pub fn calculate_fee(
     amount * fee_basis_points / 10000
 }
 
+//largest amount that, once the fee is added on top, still fits within `amount_with_fee`
+pub fn calculate_amount_before_fee(
+    amount_with_fee: u64,
+    fee_basis_points: u64,
+) -> u64 {
+    (amount_with_fee as u128 * 10000 / (10000 + fee_basis_points as u128)) as u64
+}
+
+//creator_fee_basis_points is the creator's share of the fee, returns (protocol_fee, creator_fee)
+pub fn split_fee(
+    fee: u64,
+    creator_fee_basis_points: u64,
+) -> (u64, u64) {
+    let creator_fee = calculate_fee(fee, creator_fee_basis_points);
+    (fee - creator_fee, creator_fee)
+}
+
+//curves and pools without a creator send the whole fee to the protocol
+pub fn get_fee_split(
+    creator: &Pubkey,
+    creator_fee_basis_points: u64,
+    fee: u64,
+) -> (u64, u64) {
+    if *creator == Pubkey::default() {
+        return (fee, 0);
+    }
+
+    split_fee(fee, creator_fee_basis_points)
+}
 
 #[cfg(test)]
 mod tests {
@@ -21,4 +51,26 @@ mod tests {
         assert_eq!(calculate_fee(1000, 50), 5); //0.5% fee
         assert_eq!(calculate_fee(100, 0), 0); //0% fee
     }
+
+    #[test]
+    fn test_calculate_amount_before_fee() {
+        assert_eq!(calculate_amount_before_fee(101, 100), 100); //1% fee
+        assert_eq!(calculate_amount_before_fee(110, 1000), 100); //10% fee
+        assert_eq!(calculate_amount_before_fee(1005, 50), 1000); //0.5% fee
+        assert_eq!(calculate_amount_before_fee(100, 0), 100); //0% fee
+
+        for amount in 0..10000 {
+            let before_fee = calculate_amount_before_fee(amount, 50);
+            assert!(before_fee + calculate_fee(before_fee, 50) <= amount);
+        }
+    }
+
+    #[test]
+    fn test_split_fee() {
+        assert_eq!(split_fee(100, 0), (100, 0)); //no creator share
+        assert_eq!(split_fee(100, 2500), (75, 25)); //25% creator share
+        assert_eq!(split_fee(101, 5000), (51, 50)); //rounding favours the protocol
+        assert_eq!(split_fee(100, 10000), (0, 100)); //full creator share
+        assert_eq!(split_fee(0, 5000), (0, 0));
+    }
 }
\ No newline at end of file
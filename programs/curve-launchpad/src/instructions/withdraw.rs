@@ -1,35 +1,37 @@
 use anchor_lang::prelude::*;
 use anchor_spl::{
     associated_token::AssociatedToken,
-    token::{self, Mint, Token, TokenAccount, Transfer},
+    token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked},
 };
 
 use crate::{
-    state::{BondingCurve, Global, LastWithdraw},
-    CurveLaunchpadError,
+    state::{BondingCurve, Global, WithdrawRecord},
+    CurveLaunchpadError, WithdrawEvent,
 };
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct Withdraw<'info> {
     #[account(mut)]
     user: Signer<'info>,
 
     #[account(
+        mut,
         seeds = [Global::SEED_PREFIX],
         bump,
     )]
     global: Box<Account<'info, Global>>,
 
-    mint: Account<'info, Mint>,
+    mint: InterfaceAccount<'info, Mint>,
 
     #[account(
         init_if_needed,
-        space = 8 + LastWithdraw::INIT_SPACE,
-        seeds = [LastWithdraw::SEED_PREFIX],
+        space = 8 + WithdrawRecord::INIT_SPACE,
+        seeds = [WithdrawRecord::SEED_PREFIX, mint.to_account_info().key.as_ref()],
         bump,
         payer = user,
     )]
-    last_withdraw: Box<Account<'info, LastWithdraw>>,
+    withdraw_record: Box<Account<'info, WithdrawRecord>>,
 
     #[account(
         mut,
@@ -42,22 +44,28 @@ pub struct Withdraw<'info> {
         mut,
         associated_token::mint = mint,
         associated_token::authority = bonding_curve,
+        associated_token::token_program = token_program,
     )]
-    bonding_curve_token_account: Box<Account<'info, TokenAccount>>,
+    bonding_curve_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: Using global withdraw_destinations to validate destination account
+    #[account(mut)]
+    destination: AccountInfo<'info>,
 
     #[account(
         init_if_needed,
         payer = user,
         associated_token::mint = mint,
-        associated_token::authority = user,
+        associated_token::authority = destination,
+        associated_token::token_program = token_program,
     )]
-    user_token_account: Box<Account<'info, TokenAccount>>,
+    destination_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
 
     associated_token_program: Program<'info, AssociatedToken>,
 
     system_program: Program<'info, System>,
 
-    token_program: Program<'info, Token>,
+    token_program: Interface<'info, TokenInterface>,
 }
 
 pub fn withdraw(ctx: Context<Withdraw>) -> Result<()> {
@@ -67,7 +75,7 @@ pub fn withdraw(ctx: Context<Withdraw>) -> Result<()> {
     );
 
     require!(
-        ctx.accounts.bonding_curve.complete == true,
+        ctx.accounts.bonding_curve.complete,
         CurveLaunchpadError::BondingCurveNotComplete,
     );
 
@@ -76,46 +84,109 @@ pub fn withdraw(ctx: Context<Withdraw>) -> Result<()> {
         CurveLaunchpadError::InvalidWithdrawAuthority,
     );
 
-    //transfer tokens to withdraw authority from bonding curve
-    let cpi_accounts = Transfer {
-        from: ctx
-            .accounts
-            .bonding_curve_token_account
-            .to_account_info()
-            .clone(),
-        to: ctx.accounts.user_token_account.to_account_info().clone(),
-        authority: ctx.accounts.bonding_curve.to_account_info().clone(),
-    };
+    //curves with a pool allocation can only graduate through the permissionless migrate
+    require!(
+        !ctx.accounts.bonding_curve.has_pool_allocation(),
+        CurveLaunchpadError::CurveMustMigrate,
+    );
 
-    let signer: [&[&[u8]]; 1] = [&[
-        BondingCurve::SEED_PREFIX,
-        ctx.accounts.mint.to_account_info().key.as_ref(),
-        &[ctx.bumps.bonding_curve],
-    ]];
-
-    token::transfer(
-        CpiContext::new_with_signer(
-            ctx.accounts.token_program.to_account_info(),
-            cpi_accounts,
-            &signer,
-        ),
-        ctx.accounts.bonding_curve_token_account.amount,
-    )?;
-
-    //transer sol to withdraw authority from bonding curve
-    let from_account = &ctx.accounts.bonding_curve;
-    let to_account = &ctx.accounts.user;
+    require!(
+        !ctx.accounts.global.withdraw_destinations.is_empty(),
+        CurveLaunchpadError::WithdrawDestinationsNotSet,
+    );
 
-    let min_balance = Rent::get()?.minimum_balance(8 + BondingCurve::INIT_SPACE as usize);
+    require!(
+        ctx.accounts.global.withdraw_destinations.is_allowed(ctx.accounts.destination.key),
+        CurveLaunchpadError::InvalidWithdrawDestination,
+    );
 
-    let total_bonding_curve_lamports = from_account.get_lamports() - min_balance;
+    let timestamp = Clock::get()?.unix_timestamp;
+
+    //withdraws are rate limited across every curve
+    require!(
+        !ctx.accounts.global.withdraw_limits.is_cooling_down(timestamp),
+        CurveLaunchpadError::WithdrawCooldown,
+    );
+
+    let remaining_sol = ctx.accounts.global.withdraw_limits.get_remaining_sol(timestamp);
+    require!(remaining_sol > 0, CurveLaunchpadError::WithdrawCapExceeded);
+
+    let min_balance = Rent::get()?.minimum_balance(8 + BondingCurve::INIT_SPACE);
+    let available_lamports = ctx.accounts.bonding_curve.get_lamports() - min_balance;
+
+    //anything above the window cap stays on the curve for a later withdraw
+    let total_bonding_curve_lamports = available_lamports.min(remaining_sol);
+
+    //tokens leave with the last of the SOL, so a capped withdraw never strands SOL without its tokens
+    let token_amount = if total_bonding_curve_lamports == available_lamports {
+        ctx.accounts.bonding_curve_token_account.amount
+    } else {
+        0
+    };
+
+    if token_amount > 0 {
+        //transfer tokens to destination from bonding curve
+        let cpi_accounts = TransferChecked {
+            from: ctx
+                .accounts
+                .bonding_curve_token_account
+                .to_account_info()
+                .clone(),
+            mint: ctx.accounts.mint.to_account_info(),
+            to: ctx.accounts.destination_token_account.to_account_info().clone(),
+            authority: ctx.accounts.bonding_curve.to_account_info().clone(),
+        };
+
+        let signer: [&[&[u8]]; 1] = [&[
+            BondingCurve::SEED_PREFIX,
+            ctx.accounts.mint.to_account_info().key.as_ref(),
+            &[ctx.bumps.bonding_curve],
+        ]];
+
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                cpi_accounts,
+                &signer,
+            ),
+            token_amount,
+            ctx.accounts.mint.decimals,
+        )?;
+    }
+
+    //transer sol to destination from bonding curve
+    let from_account = &ctx.accounts.bonding_curve;
+    let to_account = &ctx.accounts.destination;
 
     **from_account.to_account_info().try_borrow_mut_lamports()? -= total_bonding_curve_lamports;
     **to_account.try_borrow_mut_lamports()? += total_bonding_curve_lamports;
 
-    //update last withdraw
-    let last_withdraw = &mut ctx.accounts.last_withdraw;
-    last_withdraw.last_withdraw_timestamp = Clock::get()?.unix_timestamp;
+    ctx.accounts
+        .global
+        .withdraw_limits
+        .record_withdraw(timestamp, total_bonding_curve_lamports);
+
+    //update the withdraw record for this mint
+    let recipient = *ctx.accounts.destination.key;
+
+    let withdraw_record = &mut ctx.accounts.withdraw_record;
+    withdraw_record.mint = *ctx.accounts.mint.to_account_info().key;
+    withdraw_record.sol_withdrawn = withdraw_record
+        .sol_withdrawn
+        .saturating_add(total_bonding_curve_lamports);
+    withdraw_record.tokens_withdrawn = withdraw_record.tokens_withdrawn.saturating_add(token_amount);
+    withdraw_record.last_recipient = recipient;
+    withdraw_record.last_withdraw_timestamp = timestamp;
+
+    emit_cpi!(WithdrawEvent {
+        mint: *ctx.accounts.mint.to_account_info().key,
+        bonding_curve: *ctx.accounts.bonding_curve.to_account_info().key,
+        user: *ctx.accounts.user.to_account_info().key,
+        recipient,
+        sol_amount: total_bonding_curve_lamports,
+        token_amount,
+        timestamp,
+    });
 
     Ok(())
 }
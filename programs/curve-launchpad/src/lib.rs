@@ -1,5 +1,9 @@
 use anchor_lang::prelude::*;
 
+use amm::CurveKind;
+use state::{
+    Allowlist, CurveParams, FeeSchedule, LaunchWindow, ParamsUpdate, WithdrawDestinations,
+};
 use instructions::*;
 
 pub mod instructions;
@@ -13,26 +17,139 @@ pub mod curve_launchpad {
 
     use super::*;
 
-    pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
-        initialize::initialize(ctx)
+    pub fn initialize(ctx: Context<Initialize>, params_timelock_seconds: i64) -> Result<()> {
+        initialize::initialize(ctx, params_timelock_seconds)
     }
 
-    pub fn create(ctx: Context<Create>, name: String, symbol: String, uri: String) -> Result<()> {
-        create::create(ctx, name, symbol, uri)
+    #[allow(clippy::too_many_arguments)]
+    pub fn create(
+        ctx: Context<Create>,
+        name: String,
+        symbol: String,
+        uri: String,
+        curve_kind: CurveKind,
+        curve_params: Option<CurveParams>,
+        launch_window: Option<LaunchWindow>,
+        trading_starts_at: Option<i64>,
+        initial_buy: Option<InitialBuy>,
+        allowlist: Option<Allowlist>,
+        decimals: Option<u8>,
+    ) -> Result<()> {
+        create::create(
+            ctx,
+            name,
+            symbol,
+            uri,
+            curve_kind,
+            curve_params,
+            launch_window,
+            trading_starts_at,
+            initial_buy,
+            allowlist,
+            decimals,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_v2(
+        ctx: Context<CreateV2>,
+        name: String,
+        symbol: String,
+        uri: String,
+        curve_kind: CurveKind,
+        curve_params: Option<CurveParams>,
+        launch_window: Option<LaunchWindow>,
+        trading_starts_at: Option<i64>,
+        initial_buy: Option<InitialBuy>,
+        allowlist: Option<Allowlist>,
+        decimals: Option<u8>,
+    ) -> Result<()> {
+        create_v2::create_v2(
+            ctx,
+            name,
+            symbol,
+            uri,
+            curve_kind,
+            curve_params,
+            launch_window,
+            trading_starts_at,
+            initial_buy,
+            allowlist,
+            decimals,
+        )
     }
 
     pub fn buy(ctx: Context<Buy>, token_amount: u64, max_sol_cost: u64) -> Result<()> {
         buy::buy(ctx, token_amount, max_sol_cost)
     }
 
+    pub fn buy_allowlisted(
+        ctx: Context<Buy>,
+        token_amount: u64,
+        max_sol_cost: u64,
+        max_tokens: u64,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        buy::buy_allowlisted(ctx, token_amount, max_sol_cost, max_tokens, proof)
+    }
+
+    pub fn buy_exact_sol_in(ctx: Context<Buy>, sol_amount: u64, min_tokens_out: u64) -> Result<()> {
+        buy::buy_exact_sol_in(ctx, sol_amount, min_tokens_out)
+    }
+
     pub fn sell(ctx: Context<Sell>, token_amount: u64, min_sol_output: u64) -> Result<()> {
         sell::sell(ctx, token_amount, min_sol_output)
     }
 
+    pub fn sell_exact_sol_out(ctx: Context<Sell>, sol_amount: u64, max_tokens_in: u64) -> Result<()> {
+        sell::sell_exact_sol_out(ctx, sol_amount, max_tokens_in)
+    }
+
     pub fn withdraw(ctx: Context<Withdraw>) -> Result<()> {
         withdraw::withdraw(ctx)
     }
 
+    pub fn migrate(ctx: Context<Migrate>) -> Result<()> {
+        migrate::migrate(ctx)
+    }
+
+    pub fn swap_base_in(
+        ctx: Context<Swap>,
+        amount_in: u64,
+        minimum_amount_out: u64,
+        is_buy: bool,
+    ) -> Result<()> {
+        swap::swap_base_in(ctx, amount_in, minimum_amount_out, is_buy)
+    }
+
+    pub fn swap_base_out(
+        ctx: Context<Swap>,
+        amount_out: u64,
+        maximum_amount_in: u64,
+        is_buy: bool,
+    ) -> Result<()> {
+        swap::swap_base_out(ctx, amount_out, maximum_amount_in, is_buy)
+    }
+
+    pub fn add_liquidity(
+        ctx: Context<Liquidity>,
+        lp_amount: u64,
+        max_sol_amount: u64,
+        max_token_amount: u64,
+    ) -> Result<()> {
+        liquidity::add_liquidity(ctx, lp_amount, max_sol_amount, max_token_amount)
+    }
+
+    pub fn remove_liquidity(
+        ctx: Context<Liquidity>,
+        lp_amount: u64,
+        min_sol_amount: u64,
+        min_token_amount: u64,
+    ) -> Result<()> {
+        liquidity::remove_liquidity(ctx, lp_amount, min_sol_amount, min_token_amount)
+    }
+
+    #[allow(clippy::too_many_arguments)]
     pub fn set_params(
         ctx: Context<SetParams>,
         fee_recipient: Pubkey,
@@ -54,4 +171,148 @@ pub mod curve_launchpad {
             fee_basis_points,
         )
     }
+
+    pub fn set_allowed_curve_kinds(ctx: Context<SetParams>, allowed_curve_kinds: u8) -> Result<()> {
+        set_params::set_allowed_curve_kinds(ctx, allowed_curve_kinds)
+    }
+
+    pub fn set_curve_param_bounds(
+        ctx: Context<SetParams>,
+        min_curve_params: CurveParams,
+        max_curve_params: CurveParams,
+    ) -> Result<()> {
+        set_params::set_curve_param_bounds(ctx, min_curve_params, max_curve_params)
+    }
+
+    pub fn upgrade_bonding_curve(ctx: Context<UpgradeBondingCurve>) -> Result<()> {
+        upgrade_bonding_curve::upgrade_bonding_curve(ctx)
+    }
+
+    pub fn claim_creator_fees(ctx: Context<ClaimCreatorFees>) -> Result<()> {
+        claim_creator_fees::claim_creator_fees(ctx)
+    }
+
+    pub fn set_creator_fee(ctx: Context<SetParams>, creator_fee_basis_points: u64) -> Result<()> {
+        set_params::set_creator_fee(ctx, creator_fee_basis_points)
+    }
+
+    pub fn set_fee_schedule(ctx: Context<SetParams>, fee_schedule: FeeSchedule) -> Result<()> {
+        set_params::set_fee_schedule(ctx, fee_schedule)
+    }
+
+    pub fn set_decimals_range(
+        ctx: Context<SetParams>,
+        min_decimals: u8,
+        max_decimals: u8,
+    ) -> Result<()> {
+        set_params::set_decimals_range(ctx, min_decimals, max_decimals)
+    }
+
+    pub fn update_metadata(
+        ctx: Context<UpdateMetadata>,
+        name: Option<String>,
+        symbol: Option<String>,
+        uri: String,
+    ) -> Result<()> {
+        update_metadata::update_metadata(ctx, name, symbol, uri)
+    }
+
+    pub fn freeze_metadata(ctx: Context<UpdateMetadata>) -> Result<()> {
+        update_metadata::freeze_metadata(ctx)
+    }
+
+    pub fn revoke_freeze_authority(ctx: Context<RevokeFreezeAuthority>) -> Result<()> {
+        revoke_freeze_authority::revoke_freeze_authority(ctx)
+    }
+
+    pub fn propose_authority(ctx: Context<SetParams>, new_authority: Pubkey) -> Result<()> {
+        set_params::propose_authority(ctx, new_authority)
+    }
+
+    pub fn accept_authority(ctx: Context<SetParams>) -> Result<()> {
+        set_params::accept_authority(ctx)
+    }
+
+    pub fn set_fee_config(
+        ctx: Context<SetParams>,
+        fee_recipient: Pubkey,
+        fee_basis_points: u64,
+    ) -> Result<()> {
+        set_params::set_fee_config(ctx, fee_recipient, fee_basis_points)
+    }
+
+    pub fn set_curve_defaults(ctx: Context<SetParams>, curve_params: CurveParams) -> Result<()> {
+        set_params::set_curve_defaults(ctx, curve_params)
+    }
+
+    pub fn set_withdraw_authority(
+        ctx: Context<SetParams>,
+        withdraw_authority: Pubkey,
+    ) -> Result<()> {
+        set_params::set_withdraw_authority(ctx, withdraw_authority)
+    }
+
+    pub fn set_pauser(ctx: Context<SetParams>, pauser: Pubkey) -> Result<()> {
+        set_params::set_pauser(ctx, pauser)
+    }
+
+    pub fn pause(ctx: Context<SetPause>) -> Result<()> {
+        pause::pause(ctx)
+    }
+
+    pub fn unpause(ctx: Context<SetPause>) -> Result<()> {
+        pause::unpause(ctx)
+    }
+
+    pub fn set_params_timelock(
+        ctx: Context<SetParams>,
+        params_timelock_seconds: i64,
+    ) -> Result<()> {
+        set_params::set_params_timelock(ctx, params_timelock_seconds)
+    }
+
+    pub fn queue_params(ctx: Context<QueueParams>, params: ParamsUpdate) -> Result<()> {
+        timelock::queue_params(ctx, params)
+    }
+
+    pub fn execute_params(ctx: Context<ExecuteParams>) -> Result<()> {
+        timelock::execute_params(ctx)
+    }
+
+    pub fn cancel_params(ctx: Context<ExecuteParams>) -> Result<()> {
+        timelock::cancel_params(ctx)
+    }
+
+    pub fn set_withdraw_limits(
+        ctx: Context<SetParams>,
+        min_interval_seconds: i64,
+        window_seconds: i64,
+        window_sol_cap: u64,
+    ) -> Result<()> {
+        set_params::set_withdraw_limits(ctx, min_interval_seconds, window_seconds, window_sol_cap)
+    }
+
+    pub fn set_withdraw_destinations(
+        ctx: Context<SetParams>,
+        withdraw_destinations: WithdrawDestinations,
+    ) -> Result<()> {
+        set_params::set_withdraw_destinations(ctx, withdraw_destinations)
+    }
+
+    pub fn upgrade_global(ctx: Context<UpgradeGlobal>) -> Result<()> {
+        upgrade_global::upgrade_global(ctx)
+    }
+
+    pub fn update_metadata_v2(
+        ctx: Context<UpdateMetadataV2>,
+        name: Option<String>,
+        symbol: Option<String>,
+        uri: String,
+    ) -> Result<()> {
+        update_metadata_v2::update_metadata_v2(ctx, name, symbol, uri)
+    }
+
+    pub fn freeze_metadata_v2(ctx: Context<UpdateMetadataV2>) -> Result<()> {
+        update_metadata_v2::freeze_metadata_v2(ctx)
+    }
 }
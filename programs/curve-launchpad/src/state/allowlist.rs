@@ -0,0 +1,89 @@
+use anchor_lang::{prelude::*, solana_program::hash::hashv};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq, InitSpace)]
+pub struct Allowlist {
+    pub merkle_root: [u8; 32],
+    pub ends_at: i64,
+}
+
+impl Allowlist {
+    pub fn is_valid(&self) -> bool {
+        self.merkle_root != [0; 32] && self.ends_at > 0
+    }
+
+    //a zero root means the curve has no allowlist phase
+    pub fn is_active(&self, timestamp: i64) -> bool {
+        self.merkle_root != [0; 32] && timestamp < self.ends_at
+    }
+
+    //max_tokens of zero means the leaf has no cap
+    pub fn get_leaf(user: &Pubkey, max_tokens: u64) -> [u8; 32] {
+        hashv(&[user.as_ref(), &max_tokens.to_le_bytes()]).to_bytes()
+    }
+
+    //pairs are hashed in sorted order so proofs don't need to encode left/right
+    pub fn verify(&self, proof: &[[u8; 32]], leaf: [u8; 32]) -> bool {
+        let computed = proof.iter().fold(leaf, |node, sibling| {
+            if node <= *sibling {
+                hashv(&[&node, sibling]).to_bytes()
+            } else {
+                hashv(&[sibling, &node]).to_bytes()
+            }
+        });
+
+        computed == self.merkle_root
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hash_pair(a: [u8; 32], b: [u8; 32]) -> [u8; 32] {
+        if a <= b {
+            hashv(&[&a, &b]).to_bytes()
+        } else {
+            hashv(&[&b, &a]).to_bytes()
+        }
+    }
+
+    #[test]
+    fn test_verify() {
+        let users: Vec<Pubkey> = (0..4).map(|_| Pubkey::new_unique()).collect();
+        let leaves: Vec<[u8; 32]> = users
+            .iter()
+            .enumerate()
+            .map(|(i, user)| Allowlist::get_leaf(user, i as u64 * 1000))
+            .collect();
+
+        let left = hash_pair(leaves[0], leaves[1]);
+        let right = hash_pair(leaves[2], leaves[3]);
+        let allowlist = Allowlist {
+            merkle_root: hash_pair(left, right),
+            ends_at: 100,
+        };
+
+        assert!(allowlist.is_valid());
+        assert!(allowlist.verify(&[leaves[1], right], leaves[0]));
+        assert!(allowlist.verify(&[leaves[2], left], leaves[3]));
+
+        //wrong cap, wrong proof, and a user outside the tree
+        assert!(!allowlist.verify(&[leaves[1], right], Allowlist::get_leaf(&users[0], 1)));
+        assert!(!allowlist.verify(&[leaves[2], right], leaves[0]));
+        assert!(!allowlist.verify(&[leaves[1], right], Allowlist::get_leaf(&Pubkey::new_unique(), 0)));
+    }
+
+    #[test]
+    fn test_is_active() {
+        let allowlist = Allowlist {
+            merkle_root: [1; 32],
+            ends_at: 100,
+        };
+        assert!(allowlist.is_active(99));
+        assert!(!allowlist.is_active(100));
+
+        let allowlist = Allowlist::default();
+        assert!(!allowlist.is_valid());
+        assert!(!allowlist.is_active(0));
+    }
+}
@@ -1,6 +1,8 @@
 use anchor_lang::prelude::*;
 use std::fmt;
 
+use crate::{amm::CurveKind, state::{Allowlist, FeeSchedule, LaunchWindow}};
+
 #[account]
 #[derive(InitSpace)]
 pub struct BondingCurve {
@@ -10,23 +12,86 @@ pub struct BondingCurve {
     pub real_token_reserves: u64,
     pub token_total_supply: u64,
     pub complete: bool,
+    pub curve_kind: CurveKind,
+    pub initial_virtual_token_reserves: u64,
+    pub initial_virtual_sol_reserves: u64,
+    pub initial_real_token_reserves: u64,
+    pub fee_basis_points: u64,
+    pub creator: Pubkey,
+    pub fee_schedule: FeeSchedule,
+    pub launch_window: LaunchWindow,
+    pub trading_starts_at: i64,
+    pub allowlist: Allowlist,
+    pub paused: bool,
+    pub creator_fee_basis_points: u64,
 }
 
 impl BondingCurve {
     pub const SEED_PREFIX: &'static [u8; 13] = b"bonding-curve";
+
+    //supply the curve never sells is left on it for the pool, those curves graduate through migrate
+    pub fn has_pool_allocation(&self) -> bool {
+        self.initial_real_token_reserves < self.token_total_supply
+    }
+
+    //share of initial_real_token_reserves sold so far, in basis points
+    pub fn get_progress_basis_points(&self) -> u64 {
+        if self.initial_real_token_reserves == 0 {
+            return 0;
+        }
+
+        let sold = self.initial_real_token_reserves.saturating_sub(self.real_token_reserves);
+        (sold as u128 * 10000 / self.initial_real_token_reserves as u128) as u64
+    }
+
+    pub fn get_fee_basis_points(&self, sol_amount: u64) -> u64 {
+        self.fee_schedule.get_fee_basis_points(
+            self.fee_basis_points,
+            self.get_progress_basis_points(),
+            sol_amount,
+        )
+    }
+
+    pub fn get_buy_amount_before_fee(&self, sol_amount_with_fee: u64) -> u64 {
+        self.fee_schedule.get_buy_amount_before_fee(
+            self.fee_basis_points,
+            self.get_progress_basis_points(),
+            sol_amount_with_fee,
+        )
+    }
+
+    pub fn get_sell_amount_before_fee(&self, sol_amount_after_fee: u64) -> Option<u64> {
+        self.fee_schedule.get_sell_amount_before_fee(
+            self.fee_basis_points,
+            self.get_progress_basis_points(),
+            sol_amount_after_fee,
+        )
+    }
 }
 
 impl fmt::Display for BondingCurve {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(
             f,
-            "virtual_sol_reserves: {}, virtual_token_reserves: {}, real_sol_reserves: {}, real_token_reserves: {}, token_total_supply: {}, complete: {}",
+            "virtual_sol_reserves: {}, virtual_token_reserves: {}, real_sol_reserves: {}, real_token_reserves: {}, token_total_supply: {}, complete: {}, curve_kind: {:?}, initial_virtual_token_reserves: {}, initial_virtual_sol_reserves: {}, initial_real_token_reserves: {}, fee_basis_points: {}, creator: {}, fee_schedule: {:?}, launch_window: {:?}, trading_starts_at: {}, allowlist: {:?}, paused: {}, creator_fee_basis_points: {}",
             self.virtual_sol_reserves,
             self.virtual_token_reserves,
             self.real_sol_reserves,
             self.real_token_reserves,
             self.token_total_supply,
-            self.complete
+            self.complete,
+            self.curve_kind,
+            self.initial_virtual_token_reserves,
+            self.initial_virtual_sol_reserves,
+            self.initial_real_token_reserves,
+            self.fee_basis_points,
+            self.creator,
+            self.fee_schedule,
+            self.launch_window,
+            self.trading_starts_at,
+            self.allowlist,
+            self.paused,
+            self.creator_fee_basis_points
         )
     }
 }
@@ -0,0 +1,11 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(InitSpace)]
+pub struct CreatorVault {
+    pub creator: Pubkey,
+}
+
+impl CreatorVault {
+    pub const SEED_PREFIX: &'static [u8; 13] = b"creator-vault";
+}
@@ -0,0 +1,83 @@
+use anchor_lang::prelude::*;
+
+use crate::DEFAULT_DECIMALS;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq, InitSpace)]
+pub struct CurveParams {
+    pub initial_virtual_token_reserves: u64,
+    pub initial_virtual_sol_reserves: u64,
+    pub initial_real_token_reserves: u64,
+    pub initial_token_supply: u64,
+}
+
+impl CurveParams {
+    //the curve can only sell tokens it holds, and never more than its virtual reserves
+    pub fn is_valid(&self) -> bool {
+        self.initial_virtual_sol_reserves > 0
+            && self.initial_real_token_reserves > 0
+            && self.initial_virtual_token_reserves >= self.initial_real_token_reserves
+            && self.initial_token_supply >= self.initial_real_token_reserves
+    }
+
+    pub fn is_within(&self, min: &CurveParams, max: &CurveParams) -> bool {
+        (min.initial_virtual_token_reserves..=max.initial_virtual_token_reserves)
+            .contains(&self.initial_virtual_token_reserves)
+            && (min.initial_virtual_sol_reserves..=max.initial_virtual_sol_reserves)
+                .contains(&self.initial_virtual_sol_reserves)
+            && (min.initial_real_token_reserves..=max.initial_real_token_reserves)
+                .contains(&self.initial_real_token_reserves)
+            && (min.initial_token_supply..=max.initial_token_supply)
+                .contains(&self.initial_token_supply)
+    }
+
+    //params are configured in DEFAULT_DECIMALS units, token amounts are rescaled to the mint's decimals
+    pub fn scale_to_decimals(&self, decimals: u8) -> Option<CurveParams> {
+        let decimals = decimals as u32;
+        let scale = |amount: u64| {
+            if decimals >= DEFAULT_DECIMALS {
+                amount.checked_mul(10u64.checked_pow(decimals - DEFAULT_DECIMALS)?)
+            } else {
+                amount.checked_div(10u64.checked_pow(DEFAULT_DECIMALS - decimals)?)
+            }
+        };
+
+        Some(CurveParams {
+            initial_virtual_token_reserves: scale(self.initial_virtual_token_reserves)?,
+            initial_virtual_sol_reserves: self.initial_virtual_sol_reserves,
+            initial_real_token_reserves: scale(self.initial_real_token_reserves)?,
+            initial_token_supply: scale(self.initial_token_supply)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn default_curve_params() -> CurveParams {
+        CurveParams {
+            initial_virtual_token_reserves: 1_073_000_000_000_000,
+            initial_virtual_sol_reserves: 30_000_000_000,
+            initial_real_token_reserves: 793_100_000_000_000,
+            initial_token_supply: 1_000_000_000_000_000,
+        }
+    }
+
+    #[test]
+    fn test_scale_to_decimals() {
+        let curve_params = default_curve_params();
+        assert_eq!(curve_params.scale_to_decimals(DEFAULT_DECIMALS as u8), Some(curve_params));
+
+        let scaled = curve_params.scale_to_decimals(9).unwrap();
+        assert_eq!(scaled.initial_virtual_token_reserves, 1_073_000_000_000_000_000);
+        assert_eq!(scaled.initial_virtual_sol_reserves, 30_000_000_000); //SOL is unaffected
+        assert_eq!(scaled.initial_real_token_reserves, 793_100_000_000_000_000);
+        assert_eq!(scaled.initial_token_supply, 1_000_000_000_000_000_000);
+
+        let scaled = curve_params.scale_to_decimals(0).unwrap();
+        assert_eq!(scaled.initial_token_supply, 1_000_000_000);
+
+        //a billion tokens at 12 decimals doesn't fit in a u64
+        assert_eq!(curve_params.scale_to_decimals(12), None);
+    }
+}
@@ -0,0 +1,231 @@
+use anchor_lang::prelude::*;
+
+use crate::calculate_amount_before_fee;
+
+pub const MAX_FEE_TIERS: usize = 4;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq, InitSpace)]
+pub enum FeeScheduleKind {
+    //every trade pays the flat fee_basis_points
+    #[default]
+    Flat,
+    //tier thresholds are curve progress in basis points of initial_real_token_reserves sold
+    Progress,
+    //tier thresholds are the trade's SOL amount in lamports
+    TradeSize,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq, InitSpace)]
+pub struct FeeTier {
+    pub threshold: u64,
+    pub fee_basis_points: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq, InitSpace)]
+pub struct FeeSchedule {
+    pub kind: FeeScheduleKind,
+    pub tier_count: u8,
+    pub tiers: [FeeTier; MAX_FEE_TIERS],
+}
+
+impl FeeSchedule {
+    //tiers must be sorted by strictly increasing threshold
+    pub fn is_valid(&self) -> bool {
+        let tier_count = self.tier_count as usize;
+        if tier_count > MAX_FEE_TIERS {
+            return false;
+        }
+
+        let tiers = &self.tiers[..tier_count];
+        tiers.iter().all(|tier| tier.fee_basis_points <= 10000)
+            && tiers.windows(2).all(|pair| pair[0].threshold < pair[1].threshold)
+    }
+
+    //the last tier whose threshold has been reached applies, below the first tier the flat fee applies
+    pub fn get_fee_basis_points(
+        &self,
+        fee_basis_points: u64,
+        progress_basis_points: u64,
+        sol_amount: u64,
+    ) -> u64 {
+        let value = match self.kind {
+            FeeScheduleKind::Flat => return fee_basis_points,
+            FeeScheduleKind::Progress => progress_basis_points,
+            FeeScheduleKind::TradeSize => sol_amount,
+        };
+
+        let tier_count = (self.tier_count as usize).min(MAX_FEE_TIERS);
+        self.tiers[..tier_count]
+            .iter()
+            .rev()
+            .find(|tier| value >= tier.threshold)
+            .map_or(fee_basis_points, |tier| tier.fee_basis_points)
+    }
+
+    //tiers are always picked from the SOL moving through the curve, so exact SOL trades search
+    //for the curve amount whose own tier fee fits, rather than picking a tier from the amount with fee
+
+    //largest curve SOL amount that, with its tier's fee added on top, fits within amount_with_fee
+    pub fn get_buy_amount_before_fee(
+        &self,
+        fee_basis_points: u64,
+        progress_basis_points: u64,
+        amount_with_fee: u64,
+    ) -> u64 {
+        self.get_tier_ranges(fee_basis_points, progress_basis_points)
+            .into_iter()
+            .filter_map(|(low, high, fee_basis_points)| {
+                let amount = calculate_amount_before_fee(amount_with_fee, fee_basis_points).min(high);
+                (amount >= low).then_some(amount)
+            })
+            .max()
+            .unwrap_or(0)
+    }
+
+    //smallest curve SOL amount that, with its tier's fee taken out, still pays out amount_after_fee
+    pub fn get_sell_amount_before_fee(
+        &self,
+        fee_basis_points: u64,
+        progress_basis_points: u64,
+        amount_after_fee: u64,
+    ) -> Option<u64> {
+        self.get_tier_ranges(fee_basis_points, progress_basis_points)
+            .into_iter()
+            .filter(|(_, _, fee_basis_points)| *fee_basis_points < 10000)
+            .filter_map(|(low, high, fee_basis_points)| {
+                let amount = (amount_after_fee as u128 * 10000)
+                    .div_ceil(10000 - fee_basis_points as u128)
+                    .max(low as u128);
+                (amount <= high as u128).then_some(amount as u64)
+            })
+            .min()
+    }
+
+    //(low, high, fee_basis_points) SOL amount ranges, only trade size tiers depend on the amount
+    fn get_tier_ranges(&self, fee_basis_points: u64, progress_basis_points: u64) -> Vec<(u64, u64, u64)> {
+        if self.kind != FeeScheduleKind::TradeSize {
+            let fee_basis_points =
+                self.get_fee_basis_points(fee_basis_points, progress_basis_points, 0);
+            return vec![(0, u64::MAX, fee_basis_points)];
+        }
+
+        let tier_count = (self.tier_count as usize).min(MAX_FEE_TIERS);
+        let tiers = &self.tiers[..tier_count];
+
+        let mut ranges = Vec::with_capacity(tier_count + 1);
+        let first_threshold = tiers.first().map_or(u64::MAX, |tier| tier.threshold);
+        if first_threshold > 0 {
+            ranges.push((0, first_threshold - 1, fee_basis_points));
+        }
+        for (i, tier) in tiers.iter().enumerate() {
+            let high = tiers.get(i + 1).map_or(u64::MAX, |next| next.threshold - 1);
+            ranges.push((tier.threshold, high, tier.fee_basis_points));
+        }
+        ranges
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calculate_fee;
+
+    fn schedule(kind: FeeScheduleKind, tiers: &[(u64, u64)]) -> FeeSchedule {
+        let mut fee_schedule = FeeSchedule {
+            kind,
+            tier_count: tiers.len() as u8,
+            ..Default::default()
+        };
+        for (i, (threshold, fee_basis_points)) in tiers.iter().enumerate() {
+            fee_schedule.tiers[i] = FeeTier {
+                threshold: *threshold,
+                fee_basis_points: *fee_basis_points,
+            };
+        }
+        fee_schedule
+    }
+
+    #[test]
+    fn test_flat_fee_schedule() {
+        let fee_schedule = FeeSchedule::default();
+        assert!(fee_schedule.is_valid());
+        assert_eq!(fee_schedule.get_fee_basis_points(50, 9000, 1_000_000), 50);
+    }
+
+    #[test]
+    fn test_progress_fee_schedule() {
+        //5% until 10% sold, 1% until 90% sold, then 0.25%
+        let fee_schedule = schedule(FeeScheduleKind::Progress, &[(0, 500), (1000, 100), (9000, 25)]);
+        assert!(fee_schedule.is_valid());
+        assert_eq!(fee_schedule.get_fee_basis_points(50, 0, 1), 500);
+        assert_eq!(fee_schedule.get_fee_basis_points(50, 999, 1), 500);
+        assert_eq!(fee_schedule.get_fee_basis_points(50, 1000, 1), 100);
+        assert_eq!(fee_schedule.get_fee_basis_points(50, 9500, 1), 25);
+    }
+
+    #[test]
+    fn test_trade_size_fee_schedule() {
+        let fee_schedule = schedule(FeeScheduleKind::TradeSize, &[(1_000_000_000, 100)]);
+        assert!(fee_schedule.is_valid());
+        assert_eq!(fee_schedule.get_fee_basis_points(50, 0, 999_999_999), 50); //below first tier
+        assert_eq!(fee_schedule.get_fee_basis_points(50, 0, 1_000_000_000), 100);
+    }
+
+    #[test]
+    fn test_buy_amount_before_fee_at_tier_boundary() {
+        //1% below 1 SOL, 0.5% from 1 SOL
+        let fee_schedule = schedule(FeeScheduleKind::TradeSize, &[(1_000_000_000, 50)]);
+
+        //at 0.5% 1.004 SOL with fee only fits 0.999.. SOL, below the 0.5% tier, so the 1% tier applies
+        let amount = fee_schedule.get_buy_amount_before_fee(100, 0, 1_004_000_000);
+        assert_eq!(amount, 994_059_405);
+        assert_eq!(fee_schedule.get_fee_basis_points(100, 0, amount), 100);
+
+        //1.005 SOL with fee fits exactly 1 SOL at the 0.5% tier
+        let amount = fee_schedule.get_buy_amount_before_fee(100, 0, 1_005_000_000);
+        assert_eq!(amount, 1_000_000_000);
+        assert_eq!(fee_schedule.get_fee_basis_points(100, 0, amount), 50);
+        assert!(amount + calculate_fee(amount, 50) <= 1_005_000_000);
+
+        //5% from 1 SOL, the cheaper tier is capped just below the threshold
+        let fee_schedule = schedule(FeeScheduleKind::TradeSize, &[(1_000_000_000, 500)]);
+        let amount = fee_schedule.get_buy_amount_before_fee(100, 0, 1_020_000_000);
+        assert_eq!(amount, 999_999_999);
+        assert_eq!(fee_schedule.get_fee_basis_points(100, 0, amount), 100);
+        assert!(amount + calculate_fee(amount, 100) <= 1_020_000_000);
+    }
+
+    #[test]
+    fn test_sell_amount_before_fee_at_tier_boundary() {
+        //1% below 1 SOL, 0.5% from 1 SOL
+        let fee_schedule = schedule(FeeScheduleKind::TradeSize, &[(1_000_000_000, 50)]);
+
+        //paying out 0.992 SOL takes 1.00202.. SOL at 1%, but exactly 1 SOL is enough at 0.5%
+        let amount = fee_schedule.get_sell_amount_before_fee(100, 0, 992_000_000).unwrap();
+        assert_eq!(amount, 1_000_000_000);
+        assert_eq!(fee_schedule.get_fee_basis_points(100, 0, amount), 50);
+        assert!(amount - calculate_fee(amount, 50) >= 992_000_000);
+
+        //below the threshold the 1% tier applies to the curve amount
+        let amount = fee_schedule.get_sell_amount_before_fee(100, 0, 980_000_000).unwrap();
+        assert_eq!(amount, 989_898_990);
+        assert_eq!(fee_schedule.get_fee_basis_points(100, 0, amount), 100);
+        assert!(amount - calculate_fee(amount, 100) >= 980_000_000);
+
+        //a 100% fee can never pay out
+        let fee_schedule = schedule(FeeScheduleKind::Progress, &[(0, 10000)]);
+        assert_eq!(fee_schedule.get_sell_amount_before_fee(100, 0, 1), None);
+    }
+
+    #[test]
+    fn test_invalid_fee_schedule() {
+        assert!(!schedule(FeeScheduleKind::Progress, &[(1000, 100), (1000, 50)]).is_valid()); //not increasing
+        assert!(!schedule(FeeScheduleKind::Progress, &[(0, 10001)]).is_valid()); //over 100%
+
+        let fee_schedule = FeeSchedule {
+            tier_count: MAX_FEE_TIERS as u8 + 1,
+            ..Default::default()
+        };
+        assert!(!fee_schedule.is_valid()); //too many tiers
+    }
+}
@@ -1,5 +1,7 @@
 use anchor_lang::prelude::*;
 
+use crate::state::{CurveParams, FeeSchedule, WithdrawDestinations, WithdrawLimits};
+
 
 
 #[account]
@@ -15,8 +17,30 @@ pub struct Global {
     pub initial_token_supply: u64,
     pub fee_basis_points: u64,
     pub withdraw_authority: Pubkey,
+    pub allowed_curve_kinds: u8,
+    pub min_curve_params: CurveParams,
+    pub max_curve_params: CurveParams,
+    pub creator_fee_basis_points: u64,
+    pub fee_schedule: FeeSchedule,
+    pub min_decimals: u8,
+    pub max_decimals: u8,
+    pub pending_authority: Pubkey,
+    pub pauser: Pubkey,
+    pub paused: bool,
+    pub params_timelock_seconds: i64,
+    pub withdraw_limits: WithdrawLimits,
+    pub withdraw_destinations: WithdrawDestinations,
 }
 
 impl Global {
    pub const SEED_PREFIX: &'static [u8; 6] = b"global";
+
+   pub fn default_curve_params(&self) -> CurveParams {
+       CurveParams {
+           initial_virtual_token_reserves: self.initial_virtual_token_reserves,
+           initial_virtual_sol_reserves: self.initial_virtual_sol_reserves,
+           initial_real_token_reserves: self.initial_real_token_reserves,
+           initial_token_supply: self.initial_token_supply,
+       }
+   }
 }
\ No newline at end of file
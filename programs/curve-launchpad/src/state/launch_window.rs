@@ -0,0 +1,80 @@
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq, InitSpace)]
+pub struct LaunchWindow {
+    pub start_slot: u64,
+    pub duration_slots: u64,
+    pub max_buy_per_wallet: u64,
+}
+
+impl LaunchWindow {
+    //start_slot of a window waiting for trading to open, the first buy after it opens starts the window
+    pub const PENDING_START_SLOT: u64 = u64::MAX;
+
+    pub fn is_valid(&self) -> bool {
+        self.duration_slots > 0 && self.max_buy_per_wallet > 0
+    }
+
+    //a zero duration means the curve has no launch protection
+    pub fn is_active(&self, slot: u64) -> bool {
+        slot >= self.start_slot && slot < self.start_slot.saturating_add(self.duration_slots)
+    }
+
+    pub fn is_pending(&self) -> bool {
+        self.duration_slots > 0 && self.start_slot == Self::PENDING_START_SLOT
+    }
+
+    pub fn start(&mut self, slot: u64) {
+        if self.is_pending() {
+            self.start_slot = slot;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_active() {
+        let launch_window = LaunchWindow {
+            start_slot: 100,
+            duration_slots: 10,
+            max_buy_per_wallet: 1000,
+        };
+        assert!(launch_window.is_valid());
+        assert!(!launch_window.is_active(99));
+        assert!(launch_window.is_active(100));
+        assert!(launch_window.is_active(109));
+        assert!(!launch_window.is_active(110));
+
+        let launch_window = LaunchWindow::default();
+        assert!(!launch_window.is_valid());
+        assert!(!launch_window.is_active(0));
+    }
+
+    #[test]
+    fn test_pending_start() {
+        let mut launch_window = LaunchWindow {
+            start_slot: LaunchWindow::PENDING_START_SLOT,
+            duration_slots: 10,
+            max_buy_per_wallet: 1000,
+        };
+        assert!(launch_window.is_pending());
+        assert!(!launch_window.is_active(100));
+
+        launch_window.start(100);
+        assert!(!launch_window.is_pending());
+        assert!(launch_window.is_active(100));
+        assert!(!launch_window.is_active(110));
+
+        //a started window keeps its start slot
+        launch_window.start(200);
+        assert_eq!(launch_window.start_slot, 100);
+
+        //an unset window never becomes active
+        let mut launch_window = LaunchWindow::default();
+        launch_window.start(100);
+        assert!(!launch_window.is_active(100));
+    }
+}
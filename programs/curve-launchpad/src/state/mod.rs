@@ -1,7 +1,27 @@
 pub mod global;
 pub mod bonding_curve;
-pub mod last_withdraw;
+pub mod pool;
+pub mod curve_params;
+pub mod creator_vault;
+pub mod fee_schedule;
+pub mod launch_window;
+pub mod allowlist;
+pub mod user_purchase;
+pub mod pending_params;
+pub mod withdraw_record;
+pub mod withdraw_limits;
+pub mod withdraw_destinations;
 
 pub use global::*;
 pub use bonding_curve::*;
-pub use last_withdraw::*;
\ No newline at end of file
+pub use pool::*;
+pub use curve_params::*;
+pub use creator_vault::*;
+pub use fee_schedule::*;
+pub use launch_window::*;
+pub use allowlist::*;
+pub use user_purchase::*;
+pub use pending_params::*;
+pub use withdraw_record::*;
+pub use withdraw_limits::*;
+pub use withdraw_destinations::*;
@@ -0,0 +1,58 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{CurveParams, FeeSchedule, WithdrawDestinations};
+
+//every parameter the authority setters write, queued behind the timelock
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq, InitSpace)]
+pub struct ParamsUpdate {
+    pub fee_recipient: Pubkey,
+    pub withdraw_authority: Pubkey,
+    pub curve_params: CurveParams,
+    pub fee_basis_points: u64,
+    pub fee_schedule: FeeSchedule,
+    pub creator_fee_basis_points: u64,
+    pub allowed_curve_kinds: u8,
+    pub min_curve_params: CurveParams,
+    pub max_curve_params: CurveParams,
+    pub min_decimals: u8,
+    pub max_decimals: u8,
+    pub withdraw_min_interval_seconds: i64,
+    pub withdraw_window_seconds: i64,
+    pub withdraw_window_sol_cap: u64,
+    pub withdraw_destinations: WithdrawDestinations,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct PendingParams {
+    pub params: ParamsUpdate,
+    pub queued_at: i64,
+    pub execute_after: i64,
+}
+
+impl PendingParams {
+    pub const SEED_PREFIX: &'static [u8; 14] = b"pending-params";
+
+    pub fn is_executable(&self, now: i64) -> bool {
+        now >= self.execute_after
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_executable() {
+        let pending_params = PendingParams {
+            params: ParamsUpdate::default(),
+            queued_at: 1_000,
+            execute_after: 1_000 + 86_400,
+        };
+
+        assert!(!pending_params.is_executable(1_000));
+        assert!(!pending_params.is_executable(1_000 + 86_399));
+        assert!(pending_params.is_executable(1_000 + 86_400));
+        assert!(pending_params.is_executable(i64::MAX));
+    }
+}
@@ -0,0 +1,112 @@
+use anchor_lang::prelude::*;
+use std::fmt;
+
+use crate::state::FeeSchedule;
+
+#[account]
+#[derive(InitSpace)]
+pub struct Pool {
+    pub mint: Pubkey,
+    pub lp_mint: Pubkey,
+    pub sol_reserves: u64,
+    pub token_reserves: u64,
+    pub lp_supply: u64,
+    pub creator: Pubkey,
+    pub fee_basis_points: u64,
+    pub fee_schedule: FeeSchedule,
+    pub creator_fee_basis_points: u64,
+}
+
+impl Pool {
+    pub const SEED_PREFIX: &'static [u8; 4] = b"pool";
+    pub const LP_MINT_SEED_PREFIX: &'static [u8; 12] = b"pool-lp-mint";
+
+    //the fee is snapshotted from the curve at migrate, which has sold its whole allocation by then
+    pub const PROGRESS_BASIS_POINTS: u64 = 10000;
+
+    pub fn get_fee_basis_points(&self, sol_amount: u64) -> u64 {
+        self.fee_schedule.get_fee_basis_points(
+            self.fee_basis_points,
+            Self::PROGRESS_BASIS_POINTS,
+            sol_amount,
+        )
+    }
+
+    pub fn get_buy_amount_before_fee(&self, sol_amount_with_fee: u64) -> u64 {
+        self.fee_schedule.get_buy_amount_before_fee(
+            self.fee_basis_points,
+            Self::PROGRESS_BASIS_POINTS,
+            sol_amount_with_fee,
+        )
+    }
+
+    pub fn get_sell_amount_before_fee(&self, sol_amount_after_fee: u64) -> Option<u64> {
+        self.fee_schedule.get_sell_amount_before_fee(
+            self.fee_basis_points,
+            Self::PROGRESS_BASIS_POINTS,
+            sol_amount_after_fee,
+        )
+    }
+}
+
+impl fmt::Display for Pool {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "mint: {}, lp_mint: {}, sol_reserves: {}, token_reserves: {}, lp_supply: {}, creator: {}, fee_basis_points: {}, fee_schedule: {:?}, creator_fee_basis_points: {}",
+            self.mint,
+            self.lp_mint,
+            self.sol_reserves,
+            self.token_reserves,
+            self.lp_supply,
+            self.creator,
+            self.fee_basis_points,
+            self.fee_schedule,
+            self.creator_fee_basis_points
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::{FeeScheduleKind, FeeTier};
+
+    fn pool_with_schedule(kind: FeeScheduleKind) -> Pool {
+        let mut fee_schedule = FeeSchedule {
+            kind,
+            tier_count: 2,
+            ..Default::default()
+        };
+        fee_schedule.tiers[0] = FeeTier { threshold: 0, fee_basis_points: 500 };
+        fee_schedule.tiers[1] = FeeTier { threshold: 9000, fee_basis_points: 25 };
+
+        Pool {
+            mint: Pubkey::default(),
+            lp_mint: Pubkey::default(),
+            sol_reserves: 0,
+            token_reserves: 0,
+            lp_supply: 0,
+            creator: Pubkey::default(),
+            fee_basis_points: 50,
+            fee_schedule,
+            creator_fee_basis_points: 0,
+        }
+    }
+
+    #[test]
+    fn test_get_fee_basis_points() {
+        //progress tiers stay on the tier the curve completed with
+        let pool = pool_with_schedule(FeeScheduleKind::Progress);
+        assert_eq!(pool.get_fee_basis_points(1), 25);
+        assert_eq!(pool.get_buy_amount_before_fee(10025), 10000);
+
+        //trade size tiers still depend on the amount
+        let pool = pool_with_schedule(FeeScheduleKind::TradeSize);
+        assert_eq!(pool.get_fee_basis_points(8999), 500);
+        assert_eq!(pool.get_fee_basis_points(9000), 25);
+
+        let pool = pool_with_schedule(FeeScheduleKind::Flat);
+        assert_eq!(pool.get_fee_basis_points(9000), 50);
+    }
+}
@@ -0,0 +1,11 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(InitSpace)]
+pub struct UserPurchase {
+    pub tokens_bought: u64,
+}
+
+impl UserPurchase {
+    pub const SEED_PREFIX: &'static [u8; 13] = b"user-purchase";
+}
@@ -0,0 +1,88 @@
+use anchor_lang::prelude::*;
+
+pub const MAX_WITHDRAW_DESTINATIONS: usize = 4;
+
+//accounts withdraw may send to, withdraw is rejected until at least one is set
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq, InitSpace)]
+pub struct WithdrawDestinations {
+    pub count: u8,
+    pub destinations: [Pubkey; MAX_WITHDRAW_DESTINATIONS],
+}
+
+impl WithdrawDestinations {
+    pub fn is_valid(&self) -> bool {
+        let count = self.count as usize;
+        if count == 0 || count > MAX_WITHDRAW_DESTINATIONS {
+            return false;
+        }
+
+        let destinations = &self.destinations[..count];
+        destinations.iter().all(|destination| *destination != Pubkey::default())
+            && destinations
+                .iter()
+                .enumerate()
+                .all(|(i, destination)| !destinations[..i].contains(destination))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    pub fn is_allowed(&self, destination: &Pubkey) -> bool {
+        let count = (self.count as usize).min(MAX_WITHDRAW_DESTINATIONS);
+        self.destinations[..count].contains(destination)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_allowed() {
+        let withdraw_authority = Pubkey::new_unique();
+        let treasury = Pubkey::new_unique();
+        let migrator = Pubkey::new_unique();
+
+        //an empty list allows nothing, not even the withdraw authority
+        let withdraw_destinations = WithdrawDestinations::default();
+        assert!(withdraw_destinations.is_empty());
+        assert!(!withdraw_destinations.is_allowed(&withdraw_authority));
+        assert!(!withdraw_destinations.is_allowed(&Pubkey::default()));
+
+        let withdraw_destinations = WithdrawDestinations {
+            count: 2,
+            destinations: [treasury, migrator, Pubkey::default(), Pubkey::default()],
+        };
+        assert!(withdraw_destinations.is_valid());
+        assert!(withdraw_destinations.is_allowed(&treasury));
+        assert!(withdraw_destinations.is_allowed(&migrator));
+        assert!(!withdraw_destinations.is_allowed(&withdraw_authority));
+        assert!(!withdraw_destinations.is_allowed(&Pubkey::default()));
+    }
+
+    #[test]
+    fn test_is_valid() {
+        let treasury = Pubkey::new_unique();
+
+        assert!(!WithdrawDestinations::default().is_valid());
+
+        let withdraw_destinations = WithdrawDestinations {
+            count: 5,
+            ..Default::default()
+        };
+        assert!(!withdraw_destinations.is_valid());
+
+        let withdraw_destinations = WithdrawDestinations {
+            count: 1,
+            ..Default::default()
+        };
+        assert!(!withdraw_destinations.is_valid());
+
+        let withdraw_destinations = WithdrawDestinations {
+            count: 2,
+            destinations: [treasury, treasury, Pubkey::default(), Pubkey::default()],
+        };
+        assert!(!withdraw_destinations.is_valid());
+    }
+}
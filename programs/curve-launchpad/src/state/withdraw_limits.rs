@@ -0,0 +1,111 @@
+use anchor_lang::prelude::*;
+
+//rate limits shared by every withdraw, zero values disable the matching limit
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq, InitSpace)]
+pub struct WithdrawLimits {
+    pub min_interval_seconds: i64,
+    pub window_seconds: i64,
+    pub window_sol_cap: u64,
+    pub last_withdraw_timestamp: i64,
+    pub window_start: i64,
+    pub window_sol_withdrawn: u64,
+}
+
+impl WithdrawLimits {
+    //a window needs a cap and a cap needs a window
+    pub fn is_valid(&self) -> bool {
+        self.min_interval_seconds >= 0
+            && self.window_seconds >= 0
+            && (self.window_seconds == 0) == (self.window_sol_cap == 0)
+    }
+
+    pub fn is_cooling_down(&self, now: i64) -> bool {
+        self.min_interval_seconds > 0
+            && now < self.last_withdraw_timestamp.saturating_add(self.min_interval_seconds)
+    }
+
+    //SOL that can still be withdrawn in the current window
+    pub fn get_remaining_sol(&self, now: i64) -> u64 {
+        if self.window_seconds == 0 {
+            return u64::MAX;
+        }
+
+        if self.is_window_expired(now) {
+            self.window_sol_cap
+        } else {
+            self.window_sol_cap.saturating_sub(self.window_sol_withdrawn)
+        }
+    }
+
+    pub fn record_withdraw(&mut self, now: i64, sol_amount: u64) {
+        if self.is_window_expired(now) {
+            self.window_start = now;
+            self.window_sol_withdrawn = 0;
+        }
+
+        self.window_sol_withdrawn = self.window_sol_withdrawn.saturating_add(sol_amount);
+        self.last_withdraw_timestamp = now;
+    }
+
+    fn is_window_expired(&self, now: i64) -> bool {
+        now >= self.window_start.saturating_add(self.window_seconds)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cooldown() {
+        let mut withdraw_limits = WithdrawLimits {
+            min_interval_seconds: 60,
+            ..Default::default()
+        };
+        assert!(withdraw_limits.is_valid());
+        assert!(!withdraw_limits.is_cooling_down(1_000));
+
+        withdraw_limits.record_withdraw(1_000, 0);
+        assert!(withdraw_limits.is_cooling_down(1_059));
+        assert!(!withdraw_limits.is_cooling_down(1_060));
+    }
+
+    #[test]
+    fn test_window_cap() {
+        let now = 1_700_000_000;
+        let mut withdraw_limits = WithdrawLimits {
+            window_seconds: 3_600,
+            window_sol_cap: 100,
+            ..Default::default()
+        };
+        assert!(withdraw_limits.is_valid());
+        assert_eq!(withdraw_limits.get_remaining_sol(now), 100);
+
+        withdraw_limits.record_withdraw(now, 60);
+        assert_eq!(withdraw_limits.window_start, now);
+        assert_eq!(withdraw_limits.get_remaining_sol(now), 40);
+
+        withdraw_limits.record_withdraw(now + 1_000, 40);
+        assert_eq!(withdraw_limits.get_remaining_sol(now + 3_599), 0);
+
+        //the window restarts on the first withdraw after it expires
+        assert_eq!(withdraw_limits.get_remaining_sol(now + 3_600), 100);
+        withdraw_limits.record_withdraw(now + 3_600, 10);
+        assert_eq!(withdraw_limits.window_start, now + 3_600);
+        assert_eq!(withdraw_limits.get_remaining_sol(now + 3_600), 90);
+    }
+
+    #[test]
+    fn test_disabled() {
+        let withdraw_limits = WithdrawLimits::default();
+        assert!(withdraw_limits.is_valid());
+        assert!(!withdraw_limits.is_cooling_down(0));
+        assert_eq!(withdraw_limits.get_remaining_sol(0), u64::MAX);
+
+        let withdraw_limits = WithdrawLimits {
+            window_seconds: 3_600,
+            ..Default::default()
+        };
+        assert!(!withdraw_limits.is_valid());
+    }
+}
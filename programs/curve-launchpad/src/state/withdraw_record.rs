@@ -0,0 +1,16 @@
+use anchor_lang::prelude::*;
+
+//running totals of what the withdraw authority has taken out of a completed curve
+#[account]
+#[derive(InitSpace)]
+pub struct WithdrawRecord {
+    pub mint: Pubkey,
+    pub sol_withdrawn: u64,
+    pub tokens_withdrawn: u64,
+    pub last_recipient: Pubkey,
+    pub last_withdraw_timestamp: i64,
+}
+
+impl WithdrawRecord {
+    pub const SEED_PREFIX: &'static [u8; 15] = b"withdraw-record";
+}
@@ -7,6 +7,7 @@ import {
   fundAccountSOL,
   getAnchorError,
   getSPLBalance,
+  poolAmmFromPool,
   sendTransaction,
   toEvent,
 } from "./util";
@@ -14,14 +15,19 @@ import {
   getAssociatedTokenAddress,
   getMint,
   getOrCreateAssociatedTokenAccount,
+  getTokenMetadata,
+  TOKEN_2022_PROGRAM_ID,
+  TOKEN_PROGRAM_ID,
 } from "@solana/spl-token";
 import { BN } from "bn.js";
 import { assert } from "chai";
 import { Metaplex, token } from "@metaplex-foundation/js";
-import { AMM, calculateFee } from "../client";
+import { createHash } from "crypto";
+import { AMM, calculateAmountBeforeFee, calculateFee } from "../client";
 
 const GLOBAL_SEED = "global";
 const BONDING_CURVE_SEED = "bonding-curve";
+const BONDING_CURVE_SIZE = 8 + 253;
 
 //TODO: Unit test order is essential, need to refactor to make it so its not.
 
@@ -33,6 +39,8 @@ describe("curve-launchpad", () => {
   const DEFAULT_INITIAL_VIRTUAL_SOL_RESERVE = 30_000_000_000n;
   const DEFUALT_INITIAL_VIRTUAL_TOKEN_RESERVE = 1_073_000_000_000_000n;
   const DEFAULT_FEE_BASIS_POINTS = 50n;
+  //short enough for the suite to wait out every params change
+  const PARAMS_TIMELOCK_SECONDS = 1n;
 
   // Configure the client to use the local cluster.
   const provider = anchor.AnchorProvider.env();
@@ -48,11 +56,38 @@ describe("curve-launchpad", () => {
 
   const mint = anchor.web3.Keypair.generate();
 
+  //a second curve that completes and migrates into a pool
+  const trader = anchor.web3.Keypair.generate();
+  const poolMint = anchor.web3.Keypair.generate();
+
+  const [poolPDA] = PublicKey.findProgramAddressSync(
+    [Buffer.from("pool"), poolMint.publicKey.toBuffer()],
+    program.programId
+  );
+
+  const [lpMintPDA] = PublicKey.findProgramAddressSync(
+    [Buffer.from("pool-lp-mint"), poolMint.publicKey.toBuffer()],
+    program.programId
+  );
+
+  //a third curve that sells its whole supply, so it is withdrawn instead of migrated
+  const withdrawMint = anchor.web3.Keypair.generate();
+
+  const [withdrawBondingCurvePDA] = PublicKey.findProgramAddressSync(
+    [Buffer.from(BONDING_CURVE_SEED), withdrawMint.publicKey.toBuffer()],
+    program.programId
+  );
+
   const [globalPDA] = PublicKey.findProgramAddressSync(
     [Buffer.from(GLOBAL_SEED)],
     program.programId
   );
 
+  const [pendingParamsPDA] = PublicKey.findProgramAddressSync(
+    [Buffer.from("pending-params")],
+    program.programId
+  );
+
   const [bondingCurvePDA] = PublicKey.findProgramAddressSync(
     [Buffer.from(BONDING_CURVE_SEED), mint.publicKey.toBuffer()],
     program.programId
@@ -64,7 +99,7 @@ describe("curve-launchpad", () => {
     );
     return ammFromBondingCurve(
       bondingCurveAccount,
-      DEFUALT_INITIAL_VIRTUAL_TOKEN_RESERVE
+      BigInt(bondingCurveAccount.initialVirtualTokenReserves.toString())
     );
   };
 
@@ -96,6 +131,14 @@ describe("curve-launchpad", () => {
     assert.equal(bondingCurveAccount.complete, complete);
   };
 
+  const getUserPurchasePDA = (mint: PublicKey, user: PublicKey) => {
+    const [userPurchasePDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from("user-purchase"), mint.toBuffer(), user.toBuffer()],
+      program.programId
+    );
+    return userPurchasePDA;
+  };
+
   const simpleBuy = async (
     user: anchor.web3.Keypair,
     tokenAmount: bigint,
@@ -120,7 +163,9 @@ describe("curve-launchpad", () => {
       .accounts({
         user: user.publicKey,
         mint: mint.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
         feeRecipient: innerFeeRecipient.publicKey,
+        userPurchase: null,
         program: program.programId,
       })
       .transaction();
@@ -159,6 +204,7 @@ describe("curve-launchpad", () => {
       .accounts({
         user: user.publicKey,
         mint: mint.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
         feeRecipient: innerFeeRecipient.publicKey,
         program: program.programId,
       })
@@ -174,6 +220,135 @@ describe("curve-launchpad", () => {
     };
   };
 
+  const simpleSwap = async (
+    user: anchor.web3.Keypair,
+    baseIn: boolean,
+    amount: bigint,
+    limit: bigint,
+    isBuy: boolean
+  ) => {
+    const method = baseIn
+      ? program.methods.swapBaseIn(
+          new BN(amount.toString()),
+          new BN(limit.toString()),
+          isBuy
+        )
+      : program.methods.swapBaseOut(
+          new BN(amount.toString()),
+          new BN(limit.toString()),
+          isBuy
+        );
+
+    let tx = await method
+      .accounts({
+        user: user.publicKey,
+        mint: poolMint.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        feeRecipient: feeRecipient.publicKey,
+        program: program.programId,
+      })
+      .transaction();
+
+    return sendTransaction(program, tx, [user], user.publicKey);
+  };
+
+  const simpleLiquidity = async (
+    user: anchor.web3.Keypair,
+    isDeposit: boolean,
+    lpAmount: bigint,
+    solAmount: bigint,
+    tokenAmount: bigint
+  ) => {
+    const method = isDeposit
+      ? program.methods.addLiquidity(
+          new BN(lpAmount.toString()),
+          new BN(solAmount.toString()),
+          new BN(tokenAmount.toString())
+        )
+      : program.methods.removeLiquidity(
+          new BN(lpAmount.toString()),
+          new BN(solAmount.toString()),
+          new BN(tokenAmount.toString())
+        );
+
+    let tx = await method
+      .accounts({
+        user: user.publicKey,
+        mint: poolMint.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        program: program.programId,
+      })
+      .transaction();
+
+    return sendTransaction(program, tx, [user], user.publicKey);
+  };
+
+  //the current global params with the given ones changed
+  const getParamsUpdate = async (changes: object) => {
+    const global = await program.account.global.fetch(globalPDA);
+
+    return {
+      feeRecipient: global.feeRecipient,
+      withdrawAuthority: global.withdrawAuthority,
+      curveParams: {
+        initialVirtualTokenReserves: global.initialVirtualTokenReserves,
+        initialVirtualSolReserves: global.initialVirtualSolReserves,
+        initialRealTokenReserves: global.initialRealTokenReserves,
+        initialTokenSupply: global.initialTokenSupply,
+      },
+      feeBasisPoints: global.feeBasisPoints,
+      feeSchedule: global.feeSchedule,
+      creatorFeeBasisPoints: global.creatorFeeBasisPoints,
+      allowedCurveKinds: global.allowedCurveKinds,
+      minCurveParams: global.minCurveParams,
+      maxCurveParams: global.maxCurveParams,
+      minDecimals: global.minDecimals,
+      maxDecimals: global.maxDecimals,
+      withdrawMinIntervalSeconds: global.withdrawLimits.minIntervalSeconds,
+      withdrawWindowSeconds: global.withdrawLimits.windowSeconds,
+      withdrawWindowSolCap: global.withdrawLimits.windowSolCap,
+      withdrawDestinations: global.withdrawDestinations,
+      ...changes,
+    };
+  };
+
+  const queueParams = async (changes: object) => {
+    let tx = await program.methods
+      .queueParams(await getParamsUpdate(changes))
+      .accounts({
+        user: authority.publicKey,
+        program: program.programId,
+      })
+      .transaction();
+
+    return sendTransaction(program, tx, [authority], authority.publicKey);
+  };
+
+  //queues the change and executes it once the timelock has passed
+  const updateParams = async (changes: object) => {
+    await queueParams(changes);
+
+    const pendingParams = await program.account.pendingParams.fetch(
+      pendingParamsPDA
+    );
+    while (
+      ((await connection.getBlockTime(await connection.getSlot())) ?? 0) <=
+      pendingParams.executeAfter.toNumber()
+    ) {
+      await new Promise((resolve) => setTimeout(resolve, 500));
+    }
+
+    let tx = await program.methods
+      .executeParams()
+      .accounts({
+        user: authority.publicKey,
+        program: program.programId,
+      })
+      .transaction();
+
+    return sendTransaction(program, tx, [authority], authority.publicKey);
+  };
+
   before(async () => {
     await fundAccountSOL(connection, authority.publicKey, 5 * LAMPORTS_PER_SOL);
 
@@ -188,11 +363,13 @@ describe("curve-launchpad", () => {
       withdrawAuthority.publicKey,
       5 * LAMPORTS_PER_SOL
     );
+
+    await fundAccountSOL(connection, trader.publicKey, 400 * LAMPORTS_PER_SOL);
   });
 
   it("Is initialized!", async () => {
     await program.methods
-      .initialize()
+      .initialize(new BN(PARAMS_TIMELOCK_SECONDS.toString()))
       .accounts({
         authority: authority.publicKey,
       })
@@ -203,23 +380,48 @@ describe("curve-launchpad", () => {
 
     assert.equal(global.authority.toBase58(), authority.publicKey.toBase58());
     assert.equal(global.initialized, true);
+    assert.equal(
+      global.paramsTimelockSeconds.toString(),
+      PARAMS_TIMELOCK_SECONDS.toString()
+    );
 
-    await program.methods
-      .setParams(
-        feeRecipient.publicKey,
-        withdrawAuthority.publicKey,
-        new BN(DEFUALT_INITIAL_VIRTUAL_TOKEN_RESERVE.toString()),
-        new BN(DEFAULT_INITIAL_VIRTUAL_SOL_RESERVE.toString()),
-        new BN(DEFAULT_INITIAL_TOKEN_RESERVES.toString()),
-        new BN(DEFAULT_TOKEN_BALANCE.toString()),
-        new BN(DEFAULT_FEE_BASIS_POINTS.toString())
-      )
-      .accounts({
-        user: authority.publicKey,
-        program: program.programId,
-      })
-      .signers([authority])
-      .rpc();
+    //the defaults keep part of the supply back for the pool
+    assert.equal(
+      global.initialRealTokenReserves.toString(),
+      DEFAULT_INITIAL_TOKEN_RESERVES.toString()
+    );
+    assert.equal(
+      global.initialTokenSupply.toString(),
+      DEFAULT_TOKEN_BALANCE.toString()
+    );
+
+    const curveParams = {
+      initialVirtualTokenReserves: new BN(DEFUALT_INITIAL_VIRTUAL_TOKEN_RESERVE.toString()),
+      initialVirtualSolReserves: new BN(DEFAULT_INITIAL_VIRTUAL_SOL_RESERVE.toString()),
+      initialRealTokenReserves: new BN(DEFAULT_INITIAL_TOKEN_RESERVES.toString()),
+      initialTokenSupply: new BN(DEFAULT_TOKEN_BALANCE.toString()),
+    };
+
+    await updateParams({
+      feeRecipient: feeRecipient.publicKey,
+      withdrawAuthority: withdrawAuthority.publicKey,
+      curveParams,
+      feeBasisPoints: new BN(DEFAULT_FEE_BASIS_POINTS.toString()),
+      //allow curves that sell their whole supply as well
+      minCurveParams: {
+        ...curveParams,
+        initialTokenSupply: new BN(DEFAULT_INITIAL_TOKEN_RESERVES.toString()),
+      },
+      maxCurveParams: curveParams,
+    });
+
+    global = await program.account.global.fetch(globalPDA);
+    assert.equal(global.feeRecipient.toBase58(), feeRecipient.publicKey.toBase58());
+    assert.equal(
+      global.withdrawAuthority.toBase58(),
+      withdrawAuthority.publicKey.toBase58()
+    );
+    assert.equal(await connection.getAccountInfo(pendingParamsPDA), null);
   });
 
   it("can mint a token", async () => {
@@ -234,10 +436,11 @@ describe("curve-launchpad", () => {
     let uri = "https://www.test.com";
 
     const tx = await program.methods
-      .create(name, symbol, uri)
+      .create(name, symbol, uri, { constantProduct: {} }, null, null, null, null, null, null)
       .accounts({
         mint: mint.publicKey,
         creator: tokenCreator.publicKey,
+        feeRecipient: feeRecipient.publicKey,
         program: program.programId,
       })
       .transaction();
@@ -282,6 +485,7 @@ describe("curve-launchpad", () => {
     assert.equal(createdMint.decimals, Number(DEFAULT_DECIMALS));
     assert.equal(createdMint.supply, DEFAULT_TOKEN_BALANCE);
     assert.equal(createdMint.mintAuthority, null);
+    assert.equal(createdMint.freezeAuthority, null);
 
     const metaplex = Metaplex.make(connection);
     const token = await metaplex
@@ -324,39 +528,239 @@ describe("curve-launchpad", () => {
     assert.equal(bondingCurveAccount.complete, false);
   });
 
-  it("can buy a token", async () => {
-    let currentAMM = await getAmmFromBondingCurve();
+  it("can't mint a token with a curve kind that is not allowed", async () => {
+    const otherMint = anchor.web3.Keypair.generate();
 
-    let buyTokenAmount = DEFAULT_TOKEN_BALANCE / 100n;
-    let buyMaxSOLAmount = currentAMM.getBuyPrice(buyTokenAmount);
-    let fee = calculateFee(buyMaxSOLAmount, Number(DEFAULT_FEE_BASIS_POINTS));
-    buyMaxSOLAmount = buyMaxSOLAmount + fee;
+    let errorCode = "";
+    try {
+      const tx = await program.methods
+        .create("test", "tst", "https://www.test.com", { linear: {} }, null, null, null, null, null, null)
+        .accounts({
+          mint: otherMint.publicKey,
+          creator: tokenCreator.publicKey,
+          feeRecipient: feeRecipient.publicKey,
+          program: program.programId,
+        })
+        .transaction();
 
-    let buyResult = currentAMM.applyBuy(buyTokenAmount);
+      await sendTransaction(
+        program,
+        tx,
+        [otherMint, tokenCreator],
+        tokenCreator.publicKey
+      );
+    } catch (err) {
+      let anchorError = getAnchorError(err);
+      if (anchorError) {
+        errorCode = anchorError.error.errorCode.code;
+      }
+    }
+    assert.equal(errorCode, "CurveKindNotAllowed");
+  });
 
-    let feeRecipientPreBuySOLBalance = await connection.getBalance(
-      feeRecipient.publicKey
+  it("can't mint a token with curve params outside the global bounds", async () => {
+    const otherMint = anchor.web3.Keypair.generate();
+
+    let errorCode = "";
+    try {
+      const tx = await program.methods
+        .create("test", "tst", "https://www.test.com", { constantProduct: {} }, {
+          initialVirtualTokenReserves: new BN(DEFUALT_INITIAL_VIRTUAL_TOKEN_RESERVE.toString()),
+          initialVirtualSolReserves: new BN((DEFAULT_INITIAL_VIRTUAL_SOL_RESERVE * 2n).toString()),
+          initialRealTokenReserves: new BN(DEFAULT_INITIAL_TOKEN_RESERVES.toString()),
+          initialTokenSupply: new BN(DEFAULT_TOKEN_BALANCE.toString()),
+        }, null, null, null, null, null)
+        .accounts({
+          mint: otherMint.publicKey,
+          creator: tokenCreator.publicKey,
+          feeRecipient: feeRecipient.publicKey,
+          program: program.programId,
+        })
+        .transaction();
+
+      await sendTransaction(
+        program,
+        tx,
+        [otherMint, tokenCreator],
+        tokenCreator.publicKey
+      );
+    } catch (err) {
+      let anchorError = getAnchorError(err);
+      if (anchorError) {
+        errorCode = anchorError.error.errorCode.code;
+      }
+    }
+    assert.equal(errorCode, "InvalidCurveParams");
+  });
+
+  it("can't mint a token with decimals outside the global range", async () => {
+    const otherMint = anchor.web3.Keypair.generate();
+
+    let errorCode = "";
+    try {
+      const tx = await program.methods
+        .create(
+          "test",
+          "tst",
+          "https://www.test.com",
+          { constantProduct: {} },
+          null,
+          null,
+          null,
+          null,
+          null,
+          9
+        )
+        .accounts({
+          mint: otherMint.publicKey,
+          creator: tokenCreator.publicKey,
+          feeRecipient: feeRecipient.publicKey,
+          program: program.programId,
+        })
+        .transaction();
+
+      await sendTransaction(
+        program,
+        tx,
+        [otherMint, tokenCreator],
+        tokenCreator.publicKey
+      );
+    } catch (err) {
+      let anchorError = getAnchorError(err);
+      if (anchorError) {
+        errorCode = anchorError.error.errorCode.code;
+      }
+    }
+    assert.equal(errorCode, "InvalidDecimals");
+  });
+
+  it("can't buy more than the per-wallet cap during the launch window", async () => {
+    const protectedMint = anchor.web3.Keypair.generate();
+    const maxBuyPerWallet = DEFAULT_TOKEN_BALANCE / 1000n;
+
+    let tx = await program.methods
+      .create("test", "tst", "https://www.test.com", { constantProduct: {} }, null, {
+        startSlot: new BN(0),
+        durationSlots: new BN(1_000_000),
+        maxBuyPerWallet: new BN(maxBuyPerWallet.toString()),
+      }, null, null, null, null)
+      .accounts({
+        mint: protectedMint.publicKey,
+        creator: tokenCreator.publicKey,
+        feeRecipient: feeRecipient.publicKey,
+        program: program.programId,
+      })
+      .transaction();
+
+    await sendTransaction(
+      program,
+      tx,
+      [protectedMint, tokenCreator],
+      tokenCreator.publicKey
     );
 
-    let txResult = await simpleBuy(
+    await getOrCreateAssociatedTokenAccount(
+      connection,
       tokenCreator,
-      buyTokenAmount,
-      buyMaxSOLAmount
+      protectedMint.publicKey,
+      tokenCreator.publicKey
     );
 
-    let feeRecipientPostBuySOLBalance = await connection.getBalance(
-      feeRecipient.publicKey
-    );
-    assert.equal(
-      feeRecipientPostBuySOLBalance - feeRecipientPreBuySOLBalance,
-      Number(fee)
+    //purchases must be tracked while the launch window is active
+    let errorCode = "";
+    try {
+      let tx = await program.methods
+        .buy(new BN(1_000_000), new BN(LAMPORTS_PER_SOL.toString()))
+        .accounts({
+          user: tokenCreator.publicKey,
+          mint: protectedMint.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          feeRecipient: feeRecipient.publicKey,
+          userPurchase: null,
+          program: program.programId,
+        })
+        .transaction();
+
+      await sendTransaction(program, tx, [tokenCreator], tokenCreator.publicKey);
+    } catch (err) {
+      let anchorError = getAnchorError(err);
+      if (anchorError) {
+        errorCode = anchorError.error.errorCode.code;
+      }
+    }
+    assert.equal(errorCode, "UserPurchaseRequired");
+
+    errorCode = "";
+    try {
+      let tx = await program.methods
+        .buy(
+          new BN((maxBuyPerWallet + 1n).toString()),
+          new BN(LAMPORTS_PER_SOL.toString())
+        )
+        .accounts({
+          user: tokenCreator.publicKey,
+          mint: protectedMint.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          feeRecipient: feeRecipient.publicKey,
+          userPurchase: getUserPurchasePDA(
+            protectedMint.publicKey,
+            tokenCreator.publicKey
+          ),
+          program: program.programId,
+        })
+        .transaction();
+
+      await sendTransaction(program, tx, [tokenCreator], tokenCreator.publicKey);
+    } catch (err) {
+      let anchorError = getAnchorError(err);
+      if (anchorError) {
+        errorCode = anchorError.error.errorCode.code;
+      }
+    }
+    assert.equal(errorCode, "MaxBuyPerWalletExceeded");
+  });
+
+  it("can mint a token with a creator initial buy", async () => {
+    const bundledMint = anchor.web3.Keypair.generate();
+    const initialBuyTokenAmount = DEFAULT_TOKEN_BALANCE / 100n;
+
+    let tx = await program.methods
+      .create(
+        "test",
+        "tst",
+        "https://www.test.com",
+        { constantProduct: {} },
+        null,
+        null,
+        null,
+        {
+          initialBuyTokenAmount: new BN(initialBuyTokenAmount.toString()),
+          maxSolCost: new BN(LAMPORTS_PER_SOL.toString()),
+        },
+        null,
+        null
+      )
+      .accounts({
+        mint: bundledMint.publicKey,
+        creator: tokenCreator.publicKey,
+        feeRecipient: feeRecipient.publicKey,
+        program: program.programId,
+      })
+      .transaction();
+
+    let txResult = await sendTransaction(
+      program,
+      tx,
+      [bundledMint, tokenCreator],
+      tokenCreator.publicKey
     );
 
-    let targetCurrentSupply = (
-      DEFAULT_TOKEN_BALANCE - buyTokenAmount
-    ).toString();
+    let createEvents = txResult.events.filter((event) => {
+      return event.name === "createEvent";
+    });
+    assert.equal(createEvents.length, 1);
 
-    let tradeEvents = txResult.tx.events.filter((event) => {
+    let tradeEvents = txResult.events.filter((event) => {
       return event.name === "tradeEvent";
     });
     assert.equal(tradeEvents.length, 1);
@@ -364,357 +768,2853 @@ describe("curve-launchpad", () => {
     let tradeEvent = toEvent("tradeEvent", tradeEvents[0]);
     assert.notEqual(tradeEvent, null);
     if (tradeEvent != null) {
-      assert.equal(
-        tradeEvent.tokenAmount.toString(),
-        buyTokenAmount.toString()
-      );
-
       assert.equal(tradeEvent.isBuy, true);
       assert.equal(
-        tradeEvent.solAmount.toString(),
-        buyResult.sol_amount.toString()
+        tradeEvent.tokenAmount.toString(),
+        initialBuyTokenAmount.toString()
       );
-
       assert.equal(
-        tradeEvent.solAmount.toString(),
-        (buyMaxSOLAmount - fee).toString()
+        tradeEvent.user.toBase58(),
+        tokenCreator.publicKey.toBase58()
       );
     }
 
+    const creatorTokenAccount = await getAssociatedTokenAddress(
+      bundledMint.publicKey,
+      tokenCreator.publicKey
+    );
     const tokenAmount = await connection.getTokenAccountBalance(
-      txResult.userTokenAccount.address
+      creatorTokenAccount
+    );
+    assert.equal(tokenAmount.value.amount, initialBuyTokenAmount.toString());
+  });
+
+  it("can't buy past the per-wallet cap with a creator initial buy", async () => {
+    const maxBuyPerWallet = DEFAULT_TOKEN_BALANCE / 1000n;
+
+    const createProtected = async (
+      protectedMint: anchor.web3.Keypair,
+      initialBuyTokenAmount: bigint
+    ) => {
+      let tx = await program.methods
+        .create(
+          "test",
+          "tst",
+          "https://www.test.com",
+          { constantProduct: {} },
+          null,
+          {
+            startSlot: new BN(0),
+            durationSlots: new BN(1_000_000),
+            maxBuyPerWallet: new BN(maxBuyPerWallet.toString()),
+          },
+          null,
+          {
+            initialBuyTokenAmount: new BN(initialBuyTokenAmount.toString()),
+            maxSolCost: new BN(LAMPORTS_PER_SOL.toString()),
+          },
+          null,
+          null
+        )
+        .accounts({
+          mint: protectedMint.publicKey,
+          creator: tokenCreator.publicKey,
+          feeRecipient: feeRecipient.publicKey,
+          creatorPurchase: getUserPurchasePDA(
+            protectedMint.publicKey,
+            tokenCreator.publicKey
+          ),
+          program: program.programId,
+        })
+        .transaction();
+
+      return sendTransaction(
+        program,
+        tx,
+        [protectedMint, tokenCreator],
+        tokenCreator.publicKey
+      );
+    };
+
+    let errorCode = "";
+    try {
+      await createProtected(anchor.web3.Keypair.generate(), maxBuyPerWallet + 1n);
+    } catch (err) {
+      let anchorError = getAnchorError(err);
+      if (anchorError) {
+        errorCode = anchorError.error.errorCode.code;
+      }
+    }
+    assert.equal(errorCode, "MaxBuyPerWalletExceeded");
+
+    const protectedMint = anchor.web3.Keypair.generate();
+    await createProtected(protectedMint, maxBuyPerWallet);
+
+    //the initial buy counts toward the creator's cap
+    const userPurchasePDA = getUserPurchasePDA(
+      protectedMint.publicKey,
+      tokenCreator.publicKey
+    );
+    let userPurchase = await program.account.userPurchase.fetch(userPurchasePDA);
+    assert.equal(userPurchase.tokensBought.toString(), maxBuyPerWallet.toString());
+
+    errorCode = "";
+    try {
+      let tx = await program.methods
+        .buy(new BN(1_000_000), new BN(LAMPORTS_PER_SOL.toString()))
+        .accounts({
+          user: tokenCreator.publicKey,
+          mint: protectedMint.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          feeRecipient: feeRecipient.publicKey,
+          userPurchase: userPurchasePDA,
+          program: program.programId,
+        })
+        .transaction();
+
+      await sendTransaction(program, tx, [tokenCreator], tokenCreator.publicKey);
+    } catch (err) {
+      let anchorError = getAnchorError(err);
+      if (anchorError) {
+        errorCode = anchorError.error.errorCode.code;
+      }
+    }
+    assert.equal(errorCode, "MaxBuyPerWalletExceeded");
+  });
+
+  it("can only buy during the allowlist phase with a valid proof", async () => {
+    const allowlistMint = anchor.web3.Keypair.generate();
+    const maxTokens = DEFAULT_TOKEN_BALANCE / 1000n;
+
+    //a single leaf tree, the root is the leaf and the proof is empty
+    const maxTokensBytes = Buffer.alloc(8);
+    maxTokensBytes.writeBigUInt64LE(maxTokens);
+    const leaf = createHash("sha256")
+      .update(tokenCreator.publicKey.toBuffer())
+      .update(maxTokensBytes)
+      .digest();
+
+    let tx = await program.methods
+      .create(
+        "test",
+        "tst",
+        "https://www.test.com",
+        { constantProduct: {} },
+        null,
+        null,
+        null,
+        null,
+        {
+          merkleRoot: Array.from(leaf),
+          endsAt: new BN(Math.floor(Date.now() / 1000) + 24 * 60 * 60),
+        },
+        null
+      )
+      .accounts({
+        mint: allowlistMint.publicKey,
+        creator: tokenCreator.publicKey,
+        feeRecipient: feeRecipient.publicKey,
+        program: program.programId,
+      })
+      .transaction();
+
+    await sendTransaction(
+      program,
+      tx,
+      [allowlistMint, tokenCreator],
+      tokenCreator.publicKey
+    );
+
+    await getOrCreateAssociatedTokenAccount(
+      connection,
+      tokenCreator,
+      allowlistMint.publicKey,
+      tokenCreator.publicKey
+    );
+
+    let errorCode = "";
+    try {
+      let tx = await program.methods
+        .buy(new BN(1_000_000), new BN(LAMPORTS_PER_SOL.toString()))
+        .accounts({
+          user: tokenCreator.publicKey,
+          mint: allowlistMint.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          feeRecipient: feeRecipient.publicKey,
+          program: program.programId,
+        })
+        .transaction();
+
+      await sendTransaction(program, tx, [tokenCreator], tokenCreator.publicKey);
+    } catch (err) {
+      let anchorError = getAnchorError(err);
+      if (anchorError) {
+        errorCode = anchorError.error.errorCode.code;
+      }
+    }
+    assert.equal(errorCode, "AllowlistPhaseActive");
+
+    tx = await program.methods
+      .buyAllowlisted(
+        new BN(maxTokens.toString()),
+        new BN(LAMPORTS_PER_SOL.toString()),
+        new BN(maxTokens.toString()),
+        []
+      )
+      .accounts({
+        user: tokenCreator.publicKey,
+        mint: allowlistMint.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        feeRecipient: feeRecipient.publicKey,
+        userPurchase: getUserPurchasePDA(
+          allowlistMint.publicKey,
+          tokenCreator.publicKey
+        ),
+        program: program.programId,
+      })
+      .transaction();
+
+    let txResult = await sendTransaction(
+      program,
+      tx,
+      [tokenCreator],
+      tokenCreator.publicKey
+    );
+
+    let tradeEvents = txResult.events.filter((event) => {
+      return event.name === "tradeEvent";
+    });
+    assert.equal(tradeEvents.length, 1);
+  });
+
+  it("can mint and buy a Token-2022 token", async () => {
+    const token2022Mint = anchor.web3.Keypair.generate();
+
+    let tx = await program.methods
+      .createV2(
+        "test",
+        "tst",
+        "https://www.test.com",
+        { constantProduct: {} },
+        null,
+        null,
+        null,
+        null,
+        null,
+        null
+      )
+      .accounts({
+        mint: token2022Mint.publicKey,
+        creator: tokenCreator.publicKey,
+        feeRecipient: feeRecipient.publicKey,
+        program: program.programId,
+      })
+      .transaction();
+
+    let txResult = await sendTransaction(
+      program,
+      tx,
+      [token2022Mint, tokenCreator],
+      tokenCreator.publicKey
+    );
+
+    let createEvents = txResult.events.filter((event) => {
+      return event.name === "createEvent";
+    });
+    assert.equal(createEvents.length, 1);
+
+    const tokenMetadata = await getTokenMetadata(
+      connection,
+      token2022Mint.publicKey
+    );
+    assert.equal(tokenMetadata.name, "test");
+    assert.equal(tokenMetadata.symbol, "tst");
+    assert.equal(tokenMetadata.uri, "https://www.test.com");
+
+    const userTokenAccount = await getOrCreateAssociatedTokenAccount(
+      connection,
+      tokenCreator,
+      token2022Mint.publicKey,
+      tokenCreator.publicKey,
+      false,
+      undefined,
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+
+    const buyTokenAmount = DEFAULT_TOKEN_BALANCE / 1000n;
+
+    tx = await program.methods
+      .buy(
+        new BN(buyTokenAmount.toString()),
+        new BN(LAMPORTS_PER_SOL.toString())
+      )
+      .accounts({
+        user: tokenCreator.publicKey,
+        mint: token2022Mint.publicKey,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        feeRecipient: feeRecipient.publicKey,
+        program: program.programId,
+      })
+      .transaction();
+
+    await sendTransaction(program, tx, [tokenCreator], tokenCreator.publicKey);
+
+    const tokenAmount = await connection.getTokenAccountBalance(
+      userTokenAccount.address
     );
     assert.equal(tokenAmount.value.amount, buyTokenAmount.toString());
+  });
+
+  it("can mint a Token-2022 token with a creator initial buy", async () => {
+    const token2022Mint = anchor.web3.Keypair.generate();
+    const initialBuyTokenAmount = DEFAULT_TOKEN_BALANCE / 100n;
+
+    let tx = await program.methods
+      .createV2(
+        "test",
+        "tst",
+        "https://www.test.com",
+        { constantProduct: {} },
+        null,
+        null,
+        null,
+        {
+          initialBuyTokenAmount: new BN(initialBuyTokenAmount.toString()),
+          maxSolCost: new BN(LAMPORTS_PER_SOL.toString()),
+        },
+        null,
+        null
+      )
+      .accounts({
+        mint: token2022Mint.publicKey,
+        creator: tokenCreator.publicKey,
+        feeRecipient: feeRecipient.publicKey,
+        program: program.programId,
+      })
+      .transaction();
+
+    let txResult = await sendTransaction(
+      program,
+      tx,
+      [token2022Mint, tokenCreator],
+      tokenCreator.publicKey
+    );
+
+    let tradeEvents = txResult.events.filter((event) => {
+      return event.name === "tradeEvent";
+    });
+    assert.equal(tradeEvents.length, 1);
+
+    const creatorTokenAccount = await getAssociatedTokenAddress(
+      token2022Mint.publicKey,
+      tokenCreator.publicKey,
+      false,
+      TOKEN_2022_PROGRAM_ID
+    );
+    const tokenAmount = await connection.getTokenAccountBalance(
+      creatorTokenAccount
+    );
+    assert.equal(tokenAmount.value.amount, initialBuyTokenAmount.toString());
+  });
+
+  it("can update Token-2022 metadata as the creator", async () => {
+    const token2022Mint = anchor.web3.Keypair.generate();
+    const uri = "https://www.test.com/updated-with-a-longer-uri";
+
+    let tx = await program.methods
+      .createV2(
+        "test",
+        "tst",
+        "https://www.test.com",
+        { constantProduct: {} },
+        null,
+        null,
+        null,
+        null,
+        null,
+        null
+      )
+      .accounts({
+        mint: token2022Mint.publicKey,
+        creator: tokenCreator.publicKey,
+        feeRecipient: feeRecipient.publicKey,
+        program: program.programId,
+      })
+      .transaction();
+
+    await sendTransaction(
+      program,
+      tx,
+      [token2022Mint, tokenCreator],
+      tokenCreator.publicKey
+    );
+
+    tx = await program.methods
+      .updateMetadataV2("test updated", null, uri)
+      .accounts({
+        user: tokenCreator.publicKey,
+        mint: token2022Mint.publicKey,
+        program: program.programId,
+      })
+      .transaction();
+
+    let txResult = await sendTransaction(
+      program,
+      tx,
+      [tokenCreator],
+      tokenCreator.publicKey
+    );
+
+    let updateEvents = txResult.events.filter((event) => {
+      return event.name === "metadataUpdatedEvent";
+    });
+    assert.equal(updateEvents.length, 1);
+
+    let updateEvent = toEvent("metadataUpdatedEvent", updateEvents[0]);
+    assert.notEqual(updateEvent, null);
+    if (updateEvent != null) {
+      assert.equal(updateEvent.name, "test updated");
+      assert.equal(updateEvent.symbol, "tst");
+      assert.equal(updateEvent.uri, uri);
+      assert.equal(updateEvent.isMutable, true);
+    }
+
+    const tokenMetadata = await getTokenMetadata(
+      connection,
+      token2022Mint.publicKey
+    );
+    assert.equal(tokenMetadata.name, "test updated");
+    assert.equal(tokenMetadata.symbol, "tst");
+    assert.equal(tokenMetadata.uri, uri);
+
+    let errorCode = "";
+    try {
+      let tx = await program.methods
+        .updateMetadataV2(null, null, "https://www.test.com/hijacked")
+        .accounts({
+          user: authority.publicKey,
+          mint: token2022Mint.publicKey,
+          program: program.programId,
+        })
+        .transaction();
+
+      await sendTransaction(program, tx, [authority], authority.publicKey);
+    } catch (err) {
+      let anchorError = getAnchorError(err);
+      if (anchorError) {
+        errorCode = anchorError.error.errorCode.code;
+      }
+    }
+    assert.equal(errorCode, "InvalidCreator");
+
+    errorCode = "";
+    try {
+      let tx = await program.methods
+        .freezeMetadataV2()
+        .accounts({
+          user: authority.publicKey,
+          mint: token2022Mint.publicKey,
+          program: program.programId,
+        })
+        .transaction();
+
+      await sendTransaction(program, tx, [authority], authority.publicKey);
+    } catch (err) {
+      let anchorError = getAnchorError(err);
+      if (anchorError) {
+        errorCode = anchorError.error.errorCode.code;
+      }
+    }
+    assert.equal(errorCode, "BondingCurveNotComplete");
+  });
+
+  it("can't buy a token before trading starts", async () => {
+    const scheduledMint = anchor.web3.Keypair.generate();
+    const tradingStartsAt = Math.floor(Date.now() / 1000) + 24 * 60 * 60;
+
+    let tx = await program.methods
+      .create(
+        "test",
+        "tst",
+        "https://www.test.com",
+        { constantProduct: {} },
+        null,
+        null,
+        new BN(tradingStartsAt),
+        null,
+        null,
+        null
+      )
+      .accounts({
+        mint: scheduledMint.publicKey,
+        creator: tokenCreator.publicKey,
+        feeRecipient: feeRecipient.publicKey,
+        program: program.programId,
+      })
+      .transaction();
+
+    await sendTransaction(
+      program,
+      tx,
+      [scheduledMint, tokenCreator],
+      tokenCreator.publicKey
+    );
+
+    await getOrCreateAssociatedTokenAccount(
+      connection,
+      tokenCreator,
+      scheduledMint.publicKey,
+      tokenCreator.publicKey
+    );
+
+    let errorCode = "";
+    try {
+      let tx = await program.methods
+        .buy(new BN(1_000_000), new BN(LAMPORTS_PER_SOL.toString()))
+        .accounts({
+          user: tokenCreator.publicKey,
+          mint: scheduledMint.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          feeRecipient: feeRecipient.publicKey,
+          program: program.programId,
+        })
+        .transaction();
+
+      await sendTransaction(program, tx, [tokenCreator], tokenCreator.publicKey);
+    } catch (err) {
+      let anchorError = getAnchorError(err);
+      if (anchorError) {
+        errorCode = anchorError.error.errorCode.code;
+      }
+    }
+    assert.equal(errorCode, "TradingNotStarted");
+  });
+
+  it("can buy a token", async () => {
+    let currentAMM = await getAmmFromBondingCurve();
+
+    let buyTokenAmount = DEFAULT_TOKEN_BALANCE / 100n;
+    let buyMaxSOLAmount = currentAMM.getBuyPrice(buyTokenAmount);
+    let fee = calculateFee(buyMaxSOLAmount, Number(DEFAULT_FEE_BASIS_POINTS));
+    buyMaxSOLAmount = buyMaxSOLAmount + fee;
+
+    let buyResult = currentAMM.applyBuy(buyTokenAmount);
+
+    let feeRecipientPreBuySOLBalance = await connection.getBalance(
+      feeRecipient.publicKey
+    );
+
+    let txResult = await simpleBuy(
+      tokenCreator,
+      buyTokenAmount,
+      buyMaxSOLAmount
+    );
+
+    let feeRecipientPostBuySOLBalance = await connection.getBalance(
+      feeRecipient.publicKey
+    );
+    assert.equal(
+      feeRecipientPostBuySOLBalance - feeRecipientPreBuySOLBalance,
+      Number(fee)
+    );
+
+    //without launch protection or an allowlist no purchase record is created
+    assert.equal(
+      await connection.getAccountInfo(
+        getUserPurchasePDA(mint.publicKey, tokenCreator.publicKey)
+      ),
+      null
+    );
+
+    let targetCurrentSupply = (
+      DEFAULT_TOKEN_BALANCE - buyTokenAmount
+    ).toString();
+
+    let tradeEvents = txResult.tx.events.filter((event) => {
+      return event.name === "tradeEvent";
+    });
+    assert.equal(tradeEvents.length, 1);
+
+    let tradeEvent = toEvent("tradeEvent", tradeEvents[0]);
+    assert.notEqual(tradeEvent, null);
+    if (tradeEvent != null) {
+      assert.equal(
+        tradeEvent.tokenAmount.toString(),
+        buyTokenAmount.toString()
+      );
+
+      assert.equal(tradeEvent.isBuy, true);
+      assert.equal(
+        tradeEvent.solAmount.toString(),
+        buyResult.sol_amount.toString()
+      );
+
+      assert.equal(
+        tradeEvent.solAmount.toString(),
+        (buyMaxSOLAmount - fee).toString()
+      );
+    }
+
+    const tokenAmount = await connection.getTokenAccountBalance(
+      txResult.userTokenAccount.address
+    );
+    assert.equal(tokenAmount.value.amount, buyTokenAmount.toString());
+
+    let bondingCurveTokenAccountInfo = await connection.getTokenAccountBalance(
+      txResult.bondingCurveTokenAccount
+    );
+
+    assert.equal(
+      bondingCurveTokenAccountInfo.value.amount,
+      targetCurrentSupply
+    );
+
+    let bondingCurveAccount = await program.account.bondingCurve.fetch(
+      bondingCurvePDA
+    );
+
+    assertBondingCurve(currentAMM, bondingCurveAccount);
+  });
+
+  it("can buy a token with an exact SOL amount", async () => {
+    let currentAMM = await getAmmFromBondingCurve();
+
+    let solAmount = BigInt(LAMPORTS_PER_SOL);
+    let solAmountBeforeFee = calculateAmountBeforeFee(
+      solAmount,
+      Number(DEFAULT_FEE_BASIS_POINTS)
+    );
+    let buyTokenAmount = currentAMM.getTokensForSol(solAmountBeforeFee);
+    let buyResult = currentAMM.applyBuy(buyTokenAmount);
+    let fee = calculateFee(buyResult.sol_amount, Number(DEFAULT_FEE_BASIS_POINTS));
+
+    let userPreBuyBalance = await getSPLBalance(
+      connection,
+      mint.publicKey,
+      tokenCreator.publicKey
+    );
+
+    let tx = await program.methods
+      .buyExactSolIn(new BN(solAmount.toString()), new BN(buyTokenAmount.toString()))
+      .accounts({
+        user: tokenCreator.publicKey,
+        mint: mint.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        feeRecipient: feeRecipient.publicKey,
+        program: program.programId,
+      })
+      .transaction();
+
+    let txResult = await sendTransaction(
+      program,
+      tx,
+      [tokenCreator],
+      tokenCreator.publicKey
+    );
+
+    let tradeEvents = txResult.events.filter((event) => {
+      return event.name === "tradeEvent";
+    });
+    assert.equal(tradeEvents.length, 1);
+
+    let tradeEvent = toEvent("tradeEvent", tradeEvents[0]);
+    assert.notEqual(tradeEvent, null);
+    if (tradeEvent != null) {
+      assert.equal(tradeEvent.isBuy, true);
+      assert.equal(
+        tradeEvent.tokenAmount.toString(),
+        buyTokenAmount.toString()
+      );
+      assert.equal(
+        tradeEvent.solAmount.toString(),
+        buyResult.sol_amount.toString()
+      );
+      assert.isTrue(buyResult.sol_amount + fee <= solAmount);
+    }
+
+    let userPostBuyBalance = await getSPLBalance(
+      connection,
+      mint.publicKey,
+      tokenCreator.publicKey
+    );
+    assert.equal(
+      userPostBuyBalance,
+      (BigInt(userPreBuyBalance) + buyTokenAmount).toString()
+    );
+
+    let bondingCurveAccount = await program.account.bondingCurve.fetch(
+      bondingCurvePDA
+    );
+    assertBondingCurve(currentAMM, bondingCurveAccount);
+  });
+
+  it("can't buy a token with an exact SOL amount, min tokens out not met", async () => {
+    let errorCode = "";
+    try {
+      let tx = await program.methods
+        .buyExactSolIn(new BN(1000), new BN(DEFAULT_TOKEN_BALANCE.toString()))
+        .accounts({
+          user: tokenCreator.publicKey,
+          mint: mint.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          feeRecipient: feeRecipient.publicKey,
+          program: program.programId,
+        })
+        .transaction();
+
+      await sendTransaction(program, tx, [tokenCreator], tokenCreator.publicKey);
+    } catch (err) {
+      let anchorError = getAnchorError(err);
+      if (anchorError) {
+        errorCode = anchorError.error.errorCode.code;
+      }
+    }
+    assert.equal(errorCode, "MinTokenOutputExceeded");
+  });
+
+  it("can't buy a token with an exact SOL amount too small to buy anything", async () => {
+    let errorCode = "";
+    try {
+      let tx = await program.methods
+        .buyExactSolIn(new BN(1), new BN(0))
+        .accounts({
+          user: tokenCreator.publicKey,
+          mint: mint.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          feeRecipient: feeRecipient.publicKey,
+          program: program.programId,
+        })
+        .transaction();
+
+      await sendTransaction(program, tx, [tokenCreator], tokenCreator.publicKey);
+    } catch (err) {
+      let anchorError = getAnchorError(err);
+      if (anchorError) {
+        errorCode = anchorError.error.errorCode.code;
+      }
+    }
+    assert.equal(errorCode, "MinBuy");
+  });
+
+  it("can sell a token", async () => {
+    let currentAMM = await getAmmFromBondingCurve();
+
+    let tokenAmount = 10000000n;
+    let minSolAmount = currentAMM.getSellPrice(tokenAmount);
+    let fee = calculateFee(minSolAmount, Number(DEFAULT_FEE_BASIS_POINTS));
+    minSolAmount = minSolAmount - fee;
+
+    let sellResults = currentAMM.applySell(tokenAmount);
+
+    let userPreSaleBalance = await getSPLBalance(
+      connection,
+      mint.publicKey,
+      tokenCreator.publicKey
+    );
+
+    let curvePreSaleBalance = await getSPLBalance(
+      connection,
+      mint.publicKey,
+      bondingCurvePDA,
+      true
+    );
+
+    let feeRecipientPreBuySOLBalance = await connection.getBalance(
+      feeRecipient.publicKey
+    );
+
+    let txResult = await simpleSell(tokenCreator, tokenAmount, minSolAmount);
+
+    let feeRecipientPostBuySOLBalance = await connection.getBalance(
+      feeRecipient.publicKey
+    );
+    assert.equal(
+      feeRecipientPostBuySOLBalance - feeRecipientPreBuySOLBalance,
+      Number(fee)
+    );
+
+    let tradeEvents = txResult.tx.events.filter((event) => {
+      return event.name === "tradeEvent";
+    });
+
+    let userPostSaleBalance = await getSPLBalance(
+      connection,
+      mint.publicKey,
+      tokenCreator.publicKey
+    );
+
+    assert.equal(
+      userPostSaleBalance,
+      (BigInt(userPreSaleBalance) - tokenAmount).toString()
+    );
+    assert.equal(tradeEvents.length, 1);
+
+    let tradeEvent = toEvent("tradeEvent", tradeEvents[0]);
+    assert.notEqual(tradeEvent, null);
+    if (tradeEvent != null) {
+      assert.equal(tradeEvent.tokenAmount.toString(), tokenAmount.toString());
+      assert.equal(tradeEvent.isBuy, false);
+      assert.equal(
+        tradeEvent.solAmount.toString(),
+        sellResults.sol_amount.toString()
+      );
+    }
+
+    let curvePostSaleBalance = await getSPLBalance(
+      connection,
+      mint.publicKey,
+      bondingCurvePDA,
+      true
+    );
+
+    assert.equal(
+      curvePostSaleBalance,
+      (BigInt(curvePreSaleBalance) + tokenAmount).toString()
+    );
+
+    let bondingCurveAccount = await program.account.bondingCurve.fetch(
+      bondingCurvePDA
+    );
+    assertBondingCurve(currentAMM, bondingCurveAccount);
+  });
+
+  it("can sell a token for an exact SOL amount", async () => {
+    let currentAMM = await getAmmFromBondingCurve();
+
+    let solAmount = BigInt(LAMPORTS_PER_SOL / 10);
+    let tokenAmount = currentAMM.getTokensForSellSol(
+      solAmount,
+      DEFAULT_FEE_BASIS_POINTS
+    );
+    let sellResults = currentAMM.applySell(tokenAmount);
+    let fee = calculateFee(sellResults.sol_amount, Number(DEFAULT_FEE_BASIS_POINTS));
+
+    let userPreSaleBalance = await getSPLBalance(
+      connection,
+      mint.publicKey,
+      tokenCreator.publicKey
+    );
+    let userPreSaleSOLBalance = await connection.getBalance(
+      tokenCreator.publicKey
+    );
+    let bondingCurvePreSaleSOLBalance = await connection.getBalance(
+      bondingCurvePDA
+    );
+
+    let tx = await program.methods
+      .sellExactSolOut(new BN(solAmount.toString()), new BN(tokenAmount.toString()))
+      .accounts({
+        user: tokenCreator.publicKey,
+        mint: mint.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        feeRecipient: feeRecipient.publicKey,
+        program: program.programId,
+      })
+      .transaction();
+
+    let txResult = await sendTransaction(
+      program,
+      tx,
+      [tokenCreator],
+      tokenCreator.publicKey
+    );
+
+    let tradeEvents = txResult.events.filter((event) => {
+      return event.name === "tradeEvent";
+    });
+    assert.equal(tradeEvents.length, 1);
+
+    let tradeEvent = toEvent("tradeEvent", tradeEvents[0]);
+    assert.notEqual(tradeEvent, null);
+    if (tradeEvent != null) {
+      assert.equal(tradeEvent.isBuy, false);
+      assert.equal(tradeEvent.tokenAmount.toString(), tokenAmount.toString());
+      assert.equal(
+        tradeEvent.solAmount.toString(),
+        sellResults.sol_amount.toString()
+      );
+      assert.isTrue(sellResults.sol_amount - fee >= solAmount);
+    }
+
+    //the seller receives the SOL amount net of fees, the curve pays out the gross amount
+    let userPostSaleSOLBalance = await connection.getBalance(
+      tokenCreator.publicKey
+    );
+    assert.equal(
+      userPostSaleSOLBalance - userPreSaleSOLBalance + txResult.response.meta.fee,
+      Number(sellResults.sol_amount - fee)
+    );
+
+    let bondingCurvePostSaleSOLBalance = await connection.getBalance(
+      bondingCurvePDA
+    );
+    assert.equal(
+      bondingCurvePreSaleSOLBalance - bondingCurvePostSaleSOLBalance,
+      Number(sellResults.sol_amount)
+    );
+
+    let userPostSaleBalance = await getSPLBalance(
+      connection,
+      mint.publicKey,
+      tokenCreator.publicKey
+    );
+    assert.equal(
+      userPostSaleBalance,
+      (BigInt(userPreSaleBalance) - tokenAmount).toString()
+    );
+
+    let bondingCurveAccount = await program.account.bondingCurve.fetch(
+      bondingCurvePDA
+    );
+    assertBondingCurve(currentAMM, bondingCurveAccount);
+  });
+
+  it("can't sell a token for an exact SOL amount, max tokens in exceeded", async () => {
+    let errorCode = "";
+    try {
+      let tx = await program.methods
+        .sellExactSolOut(new BN(LAMPORTS_PER_SOL / 10), new BN(1))
+        .accounts({
+          user: tokenCreator.publicKey,
+          mint: mint.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          feeRecipient: feeRecipient.publicKey,
+          program: program.programId,
+        })
+        .transaction();
+
+      await sendTransaction(program, tx, [tokenCreator], tokenCreator.publicKey);
+    } catch (err) {
+      let anchorError = getAnchorError(err);
+      if (anchorError) {
+        errorCode = anchorError.error.errorCode.code;
+      }
+    }
+    assert.equal(errorCode, "MaxTokenInputExceeded");
+  });
+
+  //excpetion unit tests
+  it("can't withdraw as curve is incomplete", async () => {
+    let errorCode = "";
+    try {
+      let tx = await program.methods
+        .withdraw()
+        .accounts({
+          user: withdrawAuthority.publicKey,
+          destination: withdrawAuthority.publicKey,
+          mint: mint.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          program: program.programId,
+        })
+        .transaction();
+
+      await sendTransaction(
+        program,
+        tx,
+        [withdrawAuthority],
+        withdrawAuthority.publicKey
+      );
+    } catch (err) {
+      let anchorError = getAnchorError(err);
+      if (anchorError) {
+        errorCode = anchorError.error.errorCode.code;
+      }
+    }
+    assert.equal(errorCode, "BondingCurveNotComplete");
+  });
+
+  it("can't migrate as curve is incomplete", async () => {
+    let errorCode = "";
+    try {
+      let tx = await program.methods
+        .migrate()
+        .accounts({
+          user: tokenCreator.publicKey,
+          mint: mint.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          program: program.programId,
+        })
+        .transaction();
+
+      await sendTransaction(program, tx, [tokenCreator], tokenCreator.publicKey);
+    } catch (err) {
+      let anchorError = getAnchorError(err);
+      if (anchorError) {
+        errorCode = anchorError.error.errorCode.code;
+      }
+    }
+    assert.equal(errorCode, "BondingCurveNotComplete");
+  });
+
+  it("can't claim creator fees with an empty vault", async () => {
+    let errorCode = "";
+    try {
+      let tx = await program.methods
+        .claimCreatorFees()
+        .accounts({
+          creator: tokenCreator.publicKey,
+          program: program.programId,
+        })
+        .transaction();
+
+      await sendTransaction(program, tx, [tokenCreator], tokenCreator.publicKey);
+    } catch (err) {
+      let anchorError = getAnchorError(err);
+      if (anchorError) {
+        errorCode = anchorError.error.errorCode.code;
+      }
+    }
+    assert.equal(errorCode, "InsufficientSOL");
+  });
+
+  it("can't upgrade a bonding curve already on the latest layout", async () => {
+    let errorCode = "";
+    try {
+      let tx = await program.methods
+        .upgradeBondingCurve()
+        .accounts({
+          user: tokenCreator.publicKey,
+          mint: mint.publicKey,
+          program: program.programId,
+        })
+        .transaction();
+
+      await sendTransaction(program, tx, [tokenCreator], tokenCreator.publicKey);
+    } catch (err) {
+      let anchorError = getAnchorError(err);
+      if (anchorError) {
+        errorCode = anchorError.error.errorCode.code;
+      }
+    }
+    assert.equal(errorCode, "BondingCurveAlreadyUpgraded");
+  });
+
+  it("can't upgrade global already on the latest layout", async () => {
+    let errorCode = "";
+    try {
+      let tx = await program.methods
+        .upgradeGlobal()
+        .accounts({
+          user: authority.publicKey,
+          program: program.programId,
+        })
+        .transaction();
+
+      await sendTransaction(program, tx, [authority], authority.publicKey);
+    } catch (err) {
+      let anchorError = getAnchorError(err);
+      if (anchorError) {
+        errorCode = anchorError.error.errorCode.code;
+      }
+    }
+    assert.equal(errorCode, "GlobalAlreadyUpgraded");
+  });
+
+  it("can update metadata as the creator", async () => {
+    let uri = "https://www.test.com/updated";
+
+    let tx = await program.methods
+      .updateMetadata(null, null, uri)
+      .accounts({
+        user: tokenCreator.publicKey,
+        mint: mint.publicKey,
+        program: program.programId,
+      })
+      .transaction();
+
+    let txResult = await sendTransaction(
+      program,
+      tx,
+      [tokenCreator],
+      tokenCreator.publicKey
+    );
+
+    let updateEvents = txResult.events.filter((event) => {
+      return event.name === "metadataUpdatedEvent";
+    });
+    assert.equal(updateEvents.length, 1);
+
+    let updateEvent = toEvent("metadataUpdatedEvent", updateEvents[0]);
+    assert.notEqual(updateEvent, null);
+    if (updateEvent != null) {
+      assert.equal(updateEvent.name, "test");
+      assert.equal(updateEvent.symbol, "tst");
+      assert.equal(updateEvent.uri, uri);
+      assert.equal(updateEvent.isMutable, true);
+    }
+
+    const metaplex = Metaplex.make(connection);
+    const token = await metaplex
+      .nfts()
+      .findByMint({ mintAddress: mint.publicKey, loadJsonMetadata: false });
+    assert.equal(token.uri, uri);
+  });
+
+  it("can't update metadata as non-creator", async () => {
+    let errorCode = "";
+    try {
+      let tx = await program.methods
+        .updateMetadata(null, null, "https://www.test.com/hijacked")
+        .accounts({
+          user: authority.publicKey,
+          mint: mint.publicKey,
+          program: program.programId,
+        })
+        .transaction();
+
+      await sendTransaction(program, tx, [authority], authority.publicKey);
+    } catch (err) {
+      let anchorError = getAnchorError(err);
+      if (anchorError) {
+        errorCode = anchorError.error.errorCode.code;
+      }
+    }
+    assert.equal(errorCode, "InvalidCreator");
+  });
+
+  it("can't freeze metadata as curve is incomplete", async () => {
+    let errorCode = "";
+    try {
+      let tx = await program.methods
+        .freezeMetadata()
+        .accounts({
+          user: authority.publicKey,
+          mint: mint.publicKey,
+          program: program.programId,
+        })
+        .transaction();
+
+      await sendTransaction(program, tx, [authority], authority.publicKey);
+    } catch (err) {
+      let anchorError = getAnchorError(err);
+      if (anchorError) {
+        errorCode = anchorError.error.errorCode.code;
+      }
+    }
+    assert.equal(errorCode, "BondingCurveNotComplete");
+  });
+
+  it("can't revoke a freeze authority already revoked at create", async () => {
+    let errorCode = "";
+    try {
+      let tx = await program.methods
+        .revokeFreezeAuthority()
+        .accounts({
+          user: authority.publicKey,
+          mint: mint.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .transaction();
+
+      await sendTransaction(program, tx, [authority], authority.publicKey);
+    } catch (err) {
+      let anchorError = getAnchorError(err);
+      if (anchorError) {
+        errorCode = anchorError.error.errorCode.code;
+      }
+    }
+    assert.equal(errorCode, "FreezeAuthorityAlreadyRevoked");
+  });
+
+  it("can't buy a token, not enough SOL", async () => {
+    const notEnoughSolUser = anchor.web3.Keypair.generate();
+
+    await fundAccountSOL(
+      connection,
+      notEnoughSolUser.publicKey,
+      0.021 * LAMPORTS_PER_SOL
+    );
+
+    let errorCode = "";
+    try {
+      await simpleBuy(
+        notEnoughSolUser,
+        5_000_000_000_000n,
+        BigInt(5 * LAMPORTS_PER_SOL)
+      );
+    } catch (err) {
+      let anchorError = getAnchorError(err);
+      if (anchorError) {
+        errorCode = anchorError.error.errorCode.code;
+      }
+    }
+    assert.equal(errorCode, "InsufficientSOL");
+  });
+
+  it("can't buy a token, exceed max sol", async () => {
+    let errorCode = "";
+    try {
+      await simpleBuy(tokenCreator, DEFAULT_TOKEN_BALANCE / 100n, 1n);
+    } catch (err) {
+      let anchorError = getAnchorError(err);
+      if (anchorError) {
+        errorCode = anchorError.error.errorCode.code;
+      }
+    }
+    assert.equal(errorCode, "MaxSOLCostExceeded");
+  });
+
+  it("can't buy 0 tokens", async () => {
+    let errorCode = "";
+    try {
+      await simpleBuy(tokenCreator, 0n, 1n);
+    } catch (err) {
+      let anchorError = getAnchorError(err);
+      if (anchorError) {
+        errorCode = anchorError.error.errorCode.code;
+      }
+    }
+    assert.equal(errorCode, "MinBuy");
+  });
+
+  it("can't trade while paused", async () => {
+    const pauser = anchor.web3.Keypair.generate();
+    await fundAccountSOL(connection, pauser.publicKey, LAMPORTS_PER_SOL);
+
+    await program.methods
+      .setPauser(pauser.publicKey)
+      .accounts({
+        user: authority.publicKey,
+        program: program.programId,
+      })
+      .signers([authority])
+      .rpc();
+
+    //pause only this curve
+    let tx = await program.methods
+      .pause()
+      .accounts({
+        user: pauser.publicKey,
+        bondingCurve: bondingCurvePDA,
+        program: program.programId,
+      })
+      .transaction();
+
+    let txResult = await sendTransaction(program, tx, [pauser], pauser.publicKey);
+
+    let pauseEvents = txResult.events.filter((event) => {
+      return event.name === "pauseEvent";
+    });
+    assert.equal(pauseEvents.length, 1);
+
+    let pauseEvent = toEvent("pauseEvent", pauseEvents[0]);
+    assert.notEqual(pauseEvent, null);
+    if (pauseEvent != null) {
+      assert.equal(pauseEvent.bondingCurve.toBase58(), bondingCurvePDA.toBase58());
+      assert.equal(pauseEvent.paused, true);
+    }
+
+    let errorCode = "";
+    try {
+      await simpleSell(tokenCreator, 100n, 0n);
+    } catch (err) {
+      let anchorError = getAnchorError(err);
+      if (anchorError) {
+        errorCode = anchorError.error.errorCode.code;
+      }
+    }
+    assert.equal(errorCode, "Paused");
+
+    //only the authority can resume trading
+    errorCode = "";
+    try {
+      await program.methods
+        .unpause()
+        .accounts({
+          user: pauser.publicKey,
+          bondingCurve: bondingCurvePDA,
+          program: program.programId,
+        })
+        .signers([pauser])
+        .rpc();
+    } catch (err) {
+      let anchorError = getAnchorError(err);
+      if (anchorError) {
+        errorCode = anchorError.error.errorCode.code;
+      }
+    }
+    assert.equal(errorCode, "InvalidAuthority");
+
+    await program.methods
+      .unpause()
+      .accounts({
+        user: authority.publicKey,
+        bondingCurve: bondingCurvePDA,
+        program: program.programId,
+      })
+      .signers([authority])
+      .rpc();
+
+    //pause the whole program
+    await program.methods
+      .pause()
+      .accounts({
+        user: pauser.publicKey,
+        bondingCurve: null,
+        program: program.programId,
+      })
+      .signers([pauser])
+      .rpc();
+
+    errorCode = "";
+    try {
+      await simpleBuy(tokenCreator, 100n, 1_000_000n);
+    } catch (err) {
+      let anchorError = getAnchorError(err);
+      if (anchorError) {
+        errorCode = anchorError.error.errorCode.code;
+      }
+    }
+    assert.equal(errorCode, "Paused");
+
+    await program.methods
+      .unpause()
+      .accounts({
+        user: authority.publicKey,
+        bondingCurve: null,
+        program: program.programId,
+      })
+      .signers([authority])
+      .rpc();
+  });
+
+  it("can't sell a token, not enough tokens", async () => {
+    let errorCode = "";
+    try {
+      await simpleSell(tokenCreator, DEFAULT_TOKEN_BALANCE, 0n);
+    } catch (err) {
+      let anchorError = getAnchorError(err);
+      if (anchorError) {
+        errorCode = anchorError.error.errorCode.code;
+      }
+    }
+    assert.equal(errorCode, "InsufficientTokens");
+  });
+
+  it("can't sell 0 tokens", async () => {
+    let errorCode = "";
+    try {
+      await simpleSell(tokenCreator, 0n, 0n);
+    } catch (err) {
+      let anchorError = getAnchorError(err);
+      if (anchorError) {
+        errorCode = anchorError.error.errorCode.code;
+      }
+    }
+    assert.equal(errorCode, "MinSell");
+  });
+
+  it("can't sell a token, exceed mint sol sell", async () => {
+    let errorCode = "";
+    try {
+      await simpleSell(tokenCreator, 1n, DEFAULT_TOKEN_BALANCE);
+    } catch (err) {
+      let anchorError = getAnchorError(err);
+      if (anchorError) {
+        errorCode = anchorError.error.errorCode.code;
+      }
+    }
+    assert.equal(errorCode, "MinSOLOutputExceeded");
+  });
+
+  //curve complete unit tests
+  it("can complete the curve", async () => {
+    let currentAMM = await getAmmFromBondingCurve();
+    let buyTokenAmount = currentAMM.realTokenReserves;
+    let maxSolAmount = currentAMM.getBuyPrice(buyTokenAmount);
+
+    maxSolAmount =
+      maxSolAmount +
+      calculateFee(maxSolAmount, Number(DEFAULT_FEE_BASIS_POINTS));
+    let buyResult = currentAMM.applyBuy(buyTokenAmount);
+
+    let userPrePurchaseBalance = await getSPLBalance(
+      connection,
+      mint.publicKey,
+      tokenCreator.publicKey
+    );
+
+    let txResult = await simpleBuy(tokenCreator, buyTokenAmount, maxSolAmount);
+
+    let tradeEvents = txResult.tx.events.filter((event) => {
+      return event.name === "tradeEvent";
+    });
+    assert.equal(tradeEvents.length, 1);
+
+    let tradeEvent = toEvent("tradeEvent", tradeEvents[0]);
+    assert.notEqual(tradeEvent, null);
+    if (tradeEvent != null) {
+      assert.equal(tradeEvent.isBuy, true);
+      assert.equal(
+        tradeEvent.solAmount.toString(),
+        buyResult.sol_amount.toString()
+      );
+    }
+
+    let userPostPurchaseBalance = await getSPLBalance(
+      connection,
+      mint.publicKey,
+      tokenCreator.publicKey
+    );
+
+    assert.equal(
+      userPostPurchaseBalance,
+      (BigInt(userPrePurchaseBalance) + buyTokenAmount).toString()
+    );
+
+    let bondingCurveTokenAccountInfo = await connection.getTokenAccountBalance(
+      txResult.bondingCurveTokenAccount
+    );
+
+    assert.equal(
+      (
+        BigInt(bondingCurveTokenAccountInfo.value.amount) +
+        DEFAULT_INITIAL_TOKEN_RESERVES
+      ).toString(),
+      DEFAULT_TOKEN_BALANCE.toString()
+    );
+
+    let bondingCurveAccount = await program.account.bondingCurve.fetch(
+      bondingCurvePDA
+    );
+
+    assertBondingCurve(currentAMM, bondingCurveAccount, true);
+  });
+
+  it("can't buy a token, curve complete", async () => {
+    let currentAMM = await getAmmFromBondingCurve();
+
+    let buyTokenAmount = 100n;
+    let maxSolAmount = currentAMM.getBuyPrice(buyTokenAmount);
+
+    let errorCode = "";
+    try {
+      await simpleBuy(tokenCreator, buyTokenAmount, maxSolAmount);
+    } catch (err) {
+      let anchorError = getAnchorError(err);
+      if (anchorError) {
+        errorCode = anchorError.error.errorCode.code;
+      }
+    }
+    assert.equal(errorCode, "BondingCurveComplete");
+  });
+
+  it("can't sell a token, curve complete", async () => {
+    let tokenAmount = 100n;
+    let minSolAmount = 0n;
+
+    let errorCode = "";
+    try {
+      await simpleSell(tokenCreator, tokenAmount, minSolAmount);
+    } catch (err) {
+      let anchorError = getAnchorError(err);
+      if (anchorError) {
+        errorCode = anchorError.error.errorCode.code;
+      }
+    }
+    assert.equal(errorCode, "BondingCurveComplete");
+  });
+
+  it("can freeze metadata once the curve completes", async () => {
+    let tx = await program.methods
+      .freezeMetadata()
+      .accounts({
+        user: authority.publicKey,
+        mint: mint.publicKey,
+        program: program.programId,
+      })
+      .transaction();
+
+    let txResult = await sendTransaction(
+      program,
+      tx,
+      [authority],
+      authority.publicKey
+    );
+
+    let updateEvents = txResult.events.filter((event) => {
+      return event.name === "metadataUpdatedEvent";
+    });
+    assert.equal(updateEvents.length, 1);
+
+    let updateEvent = toEvent("metadataUpdatedEvent", updateEvents[0]);
+    assert.notEqual(updateEvent, null);
+    if (updateEvent != null) {
+      assert.equal(updateEvent.isMutable, false);
+    }
+
+    const metaplex = Metaplex.make(connection);
+    const token = await metaplex
+      .nfts()
+      .findByMint({ mintAddress: mint.publicKey, loadJsonMetadata: false });
+    assert.equal(token.isMutable, false);
+    assert.equal(token.updateAuthorityAddress.toBase58(), PublicKey.default.toBase58());
+  });
+
+  it("can migrate a completed curve into a pool", async () => {
+    const [poolBondingCurvePDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from(BONDING_CURVE_SEED), poolMint.publicKey.toBuffer()],
+      program.programId
+    );
+
+    let tx = await program.methods
+      .create(
+        "pool",
+        "pool",
+        "https://www.test.com",
+        { constantProduct: {} },
+        null,
+        null,
+        null,
+        null,
+        null,
+        null
+      )
+      .accounts({
+        mint: poolMint.publicKey,
+        creator: trader.publicKey,
+        feeRecipient: feeRecipient.publicKey,
+        program: program.programId,
+      })
+      .transaction();
+
+    await sendTransaction(program, tx, [poolMint, trader], trader.publicKey);
+
+    await getOrCreateAssociatedTokenAccount(
+      connection,
+      trader,
+      poolMint.publicKey,
+      trader.publicKey
+    );
+
+    let bondingCurveAccount = await program.account.bondingCurve.fetch(
+      poolBondingCurvePDA
+    );
+
+    //created with the global defaults, the unsold supply seeds the pool
+    assert.equal(
+      bondingCurveAccount.initialRealTokenReserves.toString(),
+      DEFAULT_INITIAL_TOKEN_RESERVES.toString()
+    );
+    assert.equal(
+      bondingCurveAccount.tokenTotalSupply.toString(),
+      DEFAULT_TOKEN_BALANCE.toString()
+    );
+
+    let currentAMM = ammFromBondingCurve(
+      bondingCurveAccount,
+      BigInt(bondingCurveAccount.initialVirtualTokenReserves.toString())
+    );
+    let buyTokenAmount = currentAMM.realTokenReserves;
+    let maxSolAmount = currentAMM.getBuyPrice(buyTokenAmount);
+    maxSolAmount =
+      maxSolAmount +
+      calculateFee(maxSolAmount, Number(DEFAULT_FEE_BASIS_POINTS));
+
+    tx = await program.methods
+      .buy(new BN(buyTokenAmount.toString()), new BN(maxSolAmount.toString()))
+      .accounts({
+        user: trader.publicKey,
+        mint: poolMint.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        feeRecipient: feeRecipient.publicKey,
+        userPurchase: null,
+        program: program.programId,
+      })
+      .transaction();
+
+    let txResult = await sendTransaction(
+      program,
+      tx,
+      [trader],
+      trader.publicKey
+    );
+
+    let completeEvents = txResult.events.filter((event) => {
+      return event.name === "completeEvent";
+    });
+    assert.equal(completeEvents.length, 1);
+
+    const bondingCurveTokenAccount = await getAssociatedTokenAddress(
+      poolMint.publicKey,
+      poolBondingCurvePDA,
+      true
+    );
+    let bondingCurvePreSPLBalance = await getSPLBalance(
+      connection,
+      poolMint.publicKey,
+      poolBondingCurvePDA,
+      true
+    );
+    let bondingCurvePreSOLBalance = await connection.getBalance(
+      poolBondingCurvePDA
+    );
+    let bondingCurveRent =
+      await connection.getMinimumBalanceForRentExemption(BONDING_CURVE_SIZE);
+
+    tx = await program.methods
+      .migrate()
+      .accounts({
+        user: trader.publicKey,
+        mint: poolMint.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        program: program.programId,
+      })
+      .transaction();
+
+    txResult = await sendTransaction(program, tx, [trader], trader.publicKey);
+
+    let migrateEvents = txResult.events.filter((event) => {
+      return event.name === "migrateEvent";
+    });
+    assert.equal(migrateEvents.length, 1);
+
+    let solAmount = (bondingCurvePreSOLBalance - bondingCurveRent).toString();
+
+    //pool reserves hold everything the curve had left
+    let pool = await program.account.pool.fetch(poolPDA);
+    assert.equal(pool.mint.toBase58(), poolMint.publicKey.toBase58());
+    assert.equal(pool.lpMint.toBase58(), lpMintPDA.toBase58());
+    assert.equal(pool.solReserves.toString(), solAmount);
+    assert.equal(pool.tokenReserves.toString(), bondingCurvePreSPLBalance);
+
+    let migrateEvent = toEvent("migrateEvent", migrateEvents[0]);
+    assert.notEqual(migrateEvent, null);
+    if (migrateEvent != null) {
+      assert.equal(migrateEvent.pool.toBase58(), poolPDA.toBase58());
+      assert.equal(migrateEvent.solAmount.toString(), solAmount);
+      assert.equal(
+        migrateEvent.tokenAmount.toString(),
+        bondingCurvePreSPLBalance
+      );
+      assert.equal(pool.lpSupply.toString(), migrateEvent.lpAmount.toString());
+    }
+
+    let poolTokenBalance = await getSPLBalance(
+      connection,
+      poolMint.publicKey,
+      poolPDA,
+      true
+    );
+    assert.equal(poolTokenBalance, bondingCurvePreSPLBalance);
+
+    //migrating freezes the metadata
+    const token = await Metaplex.make(connection)
+      .nfts()
+      .findByMint({ mintAddress: poolMint.publicKey, loadJsonMetadata: false });
+    assert.equal(token.isMutable, false);
+    assert.equal(token.updateAuthorityAddress.toBase58(), PublicKey.default.toBase58());
+
+    //the initial liquidity is locked, so no LP tokens are minted
+    let lpMint = await getMint(connection, lpMintPDA);
+    assert.equal(lpMint.supply.toString(), "0");
+    assert.equal(lpMint.decimals, Number(DEFAULT_DECIMALS));
+    assert.equal(lpMint.mintAuthority?.toBase58(), poolPDA.toBase58());
+
+    //the curve is left with its rent only
+    bondingCurveAccount = await program.account.bondingCurve.fetch(
+      poolBondingCurvePDA
+    );
+    assert.equal(bondingCurveAccount.realSolReserves.toString(), "0");
+    assert.equal(bondingCurveAccount.realTokenReserves.toString(), "0");
+    assert.equal(
+      await connection.getBalance(poolBondingCurvePDA),
+      bondingCurveRent
+    );
+
+    let bondingCurveTokenAccountInfo = await connection.getTokenAccountBalance(
+      bondingCurveTokenAccount
+    );
+    assert.equal(bondingCurveTokenAccountInfo.value.amount, "0");
+  });
+
+  //the withdraw authority can't take liquidity meant for the pool ahead of migrate
+  it("can't withdraw a curve that has to migrate", async () => {
+    let errorCode = "";
+    try {
+      let tx = await program.methods
+        .withdraw()
+        .accounts({
+          user: withdrawAuthority.publicKey,
+          destination: withdrawAuthority.publicKey,
+          mint: mint.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          program: program.programId,
+        })
+        .transaction();
+
+      await sendTransaction(
+        program,
+        tx,
+        [withdrawAuthority],
+        withdrawAuthority.publicKey
+      );
+    } catch (err) {
+      let anchorError = getAnchorError(err);
+      if (anchorError) {
+        errorCode = anchorError.error.errorCode.code;
+      }
+    }
+    assert.equal(errorCode, "CurveMustMigrate");
+  });
+
+  //pool swap unit tests
+  it("can buy from the pool with swap_base_in", async () => {
+    let poolAMM = poolAmmFromPool(await program.account.pool.fetch(poolPDA));
+    let solAmount = BigInt(LAMPORTS_PER_SOL);
+    let tokenAmount = poolAMM.getTokensForSol(
+      calculateAmountBeforeFee(solAmount, Number(DEFAULT_FEE_BASIS_POINTS))
+    );
+    let buyResult = poolAMM.applyBuy(tokenAmount);
+
+    let userPreBalance = await getSPLBalance(
+      connection,
+      poolMint.publicKey,
+      trader.publicKey
+    );
+
+    let txResult = await simpleSwap(trader, true, solAmount, tokenAmount, true);
+
+    let userPostBalance = await getSPLBalance(
+      connection,
+      poolMint.publicKey,
+      trader.publicKey
+    );
+    assert.equal(
+      userPostBalance,
+      (BigInt(userPreBalance) + tokenAmount).toString()
+    );
+
+    let tradeEvents = txResult.events.filter((event) => {
+      return event.name === "tradeEvent";
+    });
+    assert.equal(tradeEvents.length, 1);
+
+    let tradeEvent = toEvent("tradeEvent", tradeEvents[0]);
+    assert.notEqual(tradeEvent, null);
+    if (tradeEvent != null) {
+      assert.equal(tradeEvent.isBuy, true);
+      assert.equal(tradeEvent.mint.toBase58(), poolMint.publicKey.toBase58());
+      assert.equal(tradeEvent.user.toBase58(), trader.publicKey.toBase58());
+      assert.equal(tradeEvent.tokenAmount.toString(), tokenAmount.toString());
+      assert.equal(
+        tradeEvent.solAmount.toString(),
+        buyResult.sol_amount.toString()
+      );
+      assert.equal(
+        tradeEvent.realSolReserves.toString(),
+        poolAMM.solReserves.toString()
+      );
+      assert.equal(
+        tradeEvent.realTokenReserves.toString(),
+        poolAMM.tokenReserves.toString()
+      );
+    }
+
+    //the pool charges the fee the curve completed with and pays the creator their share
+    let tradeFeeEvents = txResult.events.filter((event) => {
+      return event.name === "tradeFeeEvent";
+    });
+    assert.equal(tradeFeeEvents.length, 1);
+
+    let tradeFeeEvent = toEvent("tradeFeeEvent", tradeFeeEvents[0]);
+    assert.notEqual(tradeFeeEvent, null);
+    if (tradeFeeEvent != null) {
+      assert.equal(tradeFeeEvent.creator.toBase58(), trader.publicKey.toBase58());
+      assert.equal(
+        tradeFeeEvent.fee.toString(),
+        calculateFee(
+          buyResult.sol_amount,
+          Number(DEFAULT_FEE_BASIS_POINTS)
+        ).toString()
+      );
+      assert.equal(
+        (
+          BigInt(tradeFeeEvent.protocolFee.toString()) +
+          BigInt(tradeFeeEvent.creatorFee.toString())
+        ).toString(),
+        tradeFeeEvent.fee.toString()
+      );
+    }
+
+    let pool = await program.account.pool.fetch(poolPDA);
+    assert.equal(pool.solReserves.toString(), poolAMM.solReserves.toString());
+    assert.equal(
+      pool.tokenReserves.toString(),
+      poolAMM.tokenReserves.toString()
+    );
+    assert.equal(pool.creator.toBase58(), trader.publicKey.toBase58());
+    assert.equal(
+      pool.feeBasisPoints.toString(),
+      DEFAULT_FEE_BASIS_POINTS.toString()
+    );
+  });
+
+  it("can sell to the pool with swap_base_in", async () => {
+    let poolAMM = poolAmmFromPool(await program.account.pool.fetch(poolPDA));
+    let tokenAmount = 1_000_000_000n;
+    let sellResult = poolAMM.applySell(tokenAmount);
+    let minSolOutput =
+      sellResult.sol_amount -
+      calculateFee(sellResult.sol_amount, Number(DEFAULT_FEE_BASIS_POINTS));
+
+    let userPreBalance = await getSPLBalance(
+      connection,
+      poolMint.publicKey,
+      trader.publicKey
+    );
+
+    let txResult = await simpleSwap(
+      trader,
+      true,
+      tokenAmount,
+      minSolOutput,
+      false
+    );
+
+    let userPostBalance = await getSPLBalance(
+      connection,
+      poolMint.publicKey,
+      trader.publicKey
+    );
+    assert.equal(
+      userPostBalance,
+      (BigInt(userPreBalance) - tokenAmount).toString()
+    );
+
+    let tradeEvents = txResult.events.filter((event) => {
+      return event.name === "tradeEvent";
+    });
+    assert.equal(tradeEvents.length, 1);
+
+    let tradeEvent = toEvent("tradeEvent", tradeEvents[0]);
+    assert.notEqual(tradeEvent, null);
+    if (tradeEvent != null) {
+      assert.equal(tradeEvent.isBuy, false);
+      assert.equal(tradeEvent.tokenAmount.toString(), tokenAmount.toString());
+      assert.equal(
+        tradeEvent.solAmount.toString(),
+        sellResult.sol_amount.toString()
+      );
+    }
+
+    let pool = await program.account.pool.fetch(poolPDA);
+    assert.equal(pool.solReserves.toString(), poolAMM.solReserves.toString());
+    assert.equal(
+      pool.tokenReserves.toString(),
+      poolAMM.tokenReserves.toString()
+    );
+  });
+
+  it("can buy and sell exact amounts with swap_base_out", async () => {
+    let poolAMM = poolAmmFromPool(await program.account.pool.fetch(poolPDA));
+    let tokenAmount = 1_000_000_000n;
+    let buyResult = poolAMM.applyBuy(tokenAmount);
+    let maxSolCost =
+      buyResult.sol_amount +
+      calculateFee(buyResult.sol_amount, Number(DEFAULT_FEE_BASIS_POINTS));
+
+    let userPreBalance = await getSPLBalance(
+      connection,
+      poolMint.publicKey,
+      trader.publicKey
+    );
+
+    await simpleSwap(trader, false, tokenAmount, maxSolCost, true);
+
+    let userPostBalance = await getSPLBalance(
+      connection,
+      poolMint.publicKey,
+      trader.publicKey
+    );
+    assert.equal(
+      userPostBalance,
+      (BigInt(userPreBalance) + tokenAmount).toString()
+    );
+
+    let solAmount = BigInt(LAMPORTS_PER_SOL) / 10n;
+    let maxTokensIn = poolAMM.getTokensForSellSol(
+      solAmount,
+      DEFAULT_FEE_BASIS_POINTS
+    );
+
+    let txResult = await simpleSwap(
+      trader,
+      false,
+      solAmount,
+      maxTokensIn,
+      false
+    );
+
+    let tradeEvents = txResult.events.filter((event) => {
+      return event.name === "tradeEvent";
+    });
+    assert.equal(tradeEvents.length, 1);
+
+    let tradeEvent = toEvent("tradeEvent", tradeEvents[0]);
+    assert.notEqual(tradeEvent, null);
+    if (tradeEvent != null) {
+      assert.equal(tradeEvent.isBuy, false);
+      assert.equal(tradeEvent.tokenAmount.toString(), maxTokensIn.toString());
+      assert.isTrue(
+        BigInt(tradeEvent.solAmount.toString()) -
+          calculateFee(
+            BigInt(tradeEvent.solAmount.toString()),
+            Number(DEFAULT_FEE_BASIS_POINTS)
+          ) >=
+          solAmount
+      );
+    }
+
+    let finalBalance = await getSPLBalance(
+      connection,
+      poolMint.publicKey,
+      trader.publicKey
+    );
+    assert.equal(
+      finalBalance,
+      (BigInt(userPostBalance) - maxTokensIn).toString()
+    );
+  });
+
+  it("can't swap past the slippage bounds", async () => {
+    let poolAMM = poolAmmFromPool(await program.account.pool.fetch(poolPDA));
+    let solAmount = BigInt(LAMPORTS_PER_SOL);
+    let tokenAmount = 1_000_000_000n;
+
+    let buyTokenAmount = poolAMM.getTokensForSol(
+      calculateAmountBeforeFee(solAmount, Number(DEFAULT_FEE_BASIS_POINTS))
+    );
+    let buyPrice = poolAMM.getBuyPrice(tokenAmount);
+    let sellPrice = poolAMM.getSellPrice(tokenAmount);
+    let sellSolOutput =
+      sellPrice - calculateFee(sellPrice, Number(DEFAULT_FEE_BASIS_POINTS));
+    let sellTokensIn = poolAMM.getTokensForSellSol(
+      solAmount,
+      DEFAULT_FEE_BASIS_POINTS
+    );
+
+    const cases: Array<[boolean, bigint, bigint, boolean, string]> = [
+      [true, solAmount, buyTokenAmount + 1n, true, "MinTokenOutputExceeded"],
+      [
+        false,
+        tokenAmount,
+        buyPrice +
+          calculateFee(buyPrice, Number(DEFAULT_FEE_BASIS_POINTS)) -
+          1n,
+        true,
+        "MaxSOLCostExceeded",
+      ],
+      [true, tokenAmount, sellSolOutput + 1n, false, "MinSOLOutputExceeded"],
+      [false, solAmount, sellTokensIn - 1n, false, "MaxTokenInputExceeded"],
+    ];
+
+    for (const [baseIn, amount, limit, isBuy, expectedErrorCode] of cases) {
+      let errorCode = "";
+      try {
+        await simpleSwap(trader, baseIn, amount, limit, isBuy);
+      } catch (err) {
+        let anchorError = getAnchorError(err);
+        if (anchorError) {
+          errorCode = anchorError.error.errorCode.code;
+        }
+      }
+      assert.equal(errorCode, expectedErrorCode);
+    }
+  });
+
+  //pool liquidity unit tests
+  it("can add liquidity to the pool", async () => {
+    let poolAMM = poolAmmFromPool(await program.account.pool.fetch(poolPDA));
+    let lpAmount = poolAMM.lpSupply / 100n;
+    let depositResult = poolAMM.applyDeposit(lpAmount);
+
+    let userPreBalance = await getSPLBalance(
+      connection,
+      poolMint.publicKey,
+      trader.publicKey
+    );
+
+    let txResult = await simpleLiquidity(
+      trader,
+      true,
+      lpAmount,
+      depositResult.sol_amount,
+      depositResult.token_amount
+    );
+
+    let liquidityEvents = txResult.events.filter((event) => {
+      return event.name === "liquidityEvent";
+    });
+    assert.equal(liquidityEvents.length, 1);
+
+    let liquidityEvent = toEvent("liquidityEvent", liquidityEvents[0]);
+    assert.notEqual(liquidityEvent, null);
+    if (liquidityEvent != null) {
+      assert.equal(liquidityEvent.isDeposit, true);
+      assert.equal(liquidityEvent.lpAmount.toString(), lpAmount.toString());
+      assert.equal(
+        liquidityEvent.solAmount.toString(),
+        depositResult.sol_amount.toString()
+      );
+      assert.equal(
+        liquidityEvent.tokenAmount.toString(),
+        depositResult.token_amount.toString()
+      );
+    }
+
+    //LP tokens are minted for the deposit
+    let userLPBalance = await getSPLBalance(
+      connection,
+      lpMintPDA,
+      trader.publicKey
+    );
+    assert.equal(userLPBalance, lpAmount.toString());
+
+    let lpMint = await getMint(connection, lpMintPDA);
+    assert.equal(lpMint.supply.toString(), lpAmount.toString());
+
+    let userPostBalance = await getSPLBalance(
+      connection,
+      poolMint.publicKey,
+      trader.publicKey
+    );
+    assert.equal(
+      userPostBalance,
+      (BigInt(userPreBalance) - depositResult.token_amount).toString()
+    );
+
+    let pool = await program.account.pool.fetch(poolPDA);
+    assert.equal(pool.solReserves.toString(), poolAMM.solReserves.toString());
+    assert.equal(
+      pool.tokenReserves.toString(),
+      poolAMM.tokenReserves.toString()
+    );
+    assert.equal(pool.lpSupply.toString(), poolAMM.lpSupply.toString());
+  });
+
+  it("can remove liquidity from the pool", async () => {
+    let poolAMM = poolAmmFromPool(await program.account.pool.fetch(poolPDA));
+    let userPreLPBalance = BigInt(
+      await getSPLBalance(connection, lpMintPDA, trader.publicKey)
+    );
+    let lpAmount = userPreLPBalance / 2n;
+    let withdrawResult = poolAMM.applyWithdraw(lpAmount);
+
+    let userPreBalance = await getSPLBalance(
+      connection,
+      poolMint.publicKey,
+      trader.publicKey
+    );
 
-    let bondingCurveTokenAccountInfo = await connection.getTokenAccountBalance(
-      txResult.bondingCurveTokenAccount
+    let txResult = await simpleLiquidity(
+      trader,
+      false,
+      lpAmount,
+      withdrawResult.sol_amount,
+      withdrawResult.token_amount
+    );
+
+    let liquidityEvents = txResult.events.filter((event) => {
+      return event.name === "liquidityEvent";
+    });
+    assert.equal(liquidityEvents.length, 1);
+
+    let liquidityEvent = toEvent("liquidityEvent", liquidityEvents[0]);
+    assert.notEqual(liquidityEvent, null);
+    if (liquidityEvent != null) {
+      assert.equal(liquidityEvent.isDeposit, false);
+      assert.equal(liquidityEvent.lpAmount.toString(), lpAmount.toString());
+      assert.equal(
+        liquidityEvent.solAmount.toString(),
+        withdrawResult.sol_amount.toString()
+      );
+      assert.equal(
+        liquidityEvent.tokenAmount.toString(),
+        withdrawResult.token_amount.toString()
+      );
+    }
+
+    //the returned LP tokens are burned
+    let userPostLPBalance = await getSPLBalance(
+      connection,
+      lpMintPDA,
+      trader.publicKey
     );
+    assert.equal(userPostLPBalance, (userPreLPBalance - lpAmount).toString());
 
+    let lpMint = await getMint(connection, lpMintPDA);
     assert.equal(
-      bondingCurveTokenAccountInfo.value.amount,
-      targetCurrentSupply
+      lpMint.supply.toString(),
+      (userPreLPBalance - lpAmount).toString()
     );
 
-    let bondingCurveAccount = await program.account.bondingCurve.fetch(
-      bondingCurvePDA
+    let userPostBalance = await getSPLBalance(
+      connection,
+      poolMint.publicKey,
+      trader.publicKey
+    );
+    assert.equal(
+      userPostBalance,
+      (BigInt(userPreBalance) + withdrawResult.token_amount).toString()
     );
 
-    assertBondingCurve(currentAMM, bondingCurveAccount);
+    let pool = await program.account.pool.fetch(poolPDA);
+    assert.equal(pool.solReserves.toString(), poolAMM.solReserves.toString());
+    assert.equal(
+      pool.tokenReserves.toString(),
+      poolAMM.tokenReserves.toString()
+    );
+    assert.equal(pool.lpSupply.toString(), poolAMM.lpSupply.toString());
   });
 
-  it("can sell a token", async () => {
-    let currentAMM = await getAmmFromBondingCurve();
+  it("can't add or remove liquidity past the slippage bounds", async () => {
+    let poolAMM = poolAmmFromPool(await program.account.pool.fetch(poolPDA));
+    let lpAmount = BigInt(
+      await getSPLBalance(connection, lpMintPDA, trader.publicKey)
+    );
 
-    let tokenAmount = 10000000n;
-    let minSolAmount = currentAMM.getSellPrice(tokenAmount);
-    let fee = calculateFee(minSolAmount, Number(DEFAULT_FEE_BASIS_POINTS));
-    minSolAmount = minSolAmount - fee;
+    let [depositSolAmount, depositTokenAmount] =
+      poolAMM.getDepositAmounts(lpAmount);
+    let [withdrawSolAmount, withdrawTokenAmount] =
+      poolAMM.getWithdrawAmounts(lpAmount);
+
+    const cases: Array<[boolean, bigint, bigint, string]> = [
+      [true, depositSolAmount - 1n, depositTokenAmount, "MaxSOLCostExceeded"],
+      [
+        true,
+        depositSolAmount,
+        depositTokenAmount - 1n,
+        "MaxTokenInputExceeded",
+      ],
+      [
+        false,
+        withdrawSolAmount + 1n,
+        withdrawTokenAmount,
+        "MinSOLOutputExceeded",
+      ],
+      [
+        false,
+        withdrawSolAmount,
+        withdrawTokenAmount + 1n,
+        "MinTokenOutputExceeded",
+      ],
+    ];
+
+    for (const [isDeposit, solAmount, tokenAmount, expectedErrorCode] of cases) {
+      let errorCode = "";
+      try {
+        await simpleLiquidity(
+          trader,
+          isDeposit,
+          lpAmount,
+          solAmount,
+          tokenAmount
+        );
+      } catch (err) {
+        let anchorError = getAnchorError(err);
+        if (anchorError) {
+          errorCode = anchorError.error.errorCode.code;
+        }
+      }
+      assert.equal(errorCode, expectedErrorCode);
+    }
+  });
 
-    let sellResults = currentAMM.applySell(tokenAmount);
+  it("can complete a curve without a pool allocation", async () => {
+    let tx = await program.methods
+      .create(
+        "withdraw",
+        "wdr",
+        "https://www.test.com",
+        { constantProduct: {} },
+        {
+          initialVirtualTokenReserves: new BN(DEFUALT_INITIAL_VIRTUAL_TOKEN_RESERVE.toString()),
+          initialVirtualSolReserves: new BN(DEFAULT_INITIAL_VIRTUAL_SOL_RESERVE.toString()),
+          initialRealTokenReserves: new BN(DEFAULT_INITIAL_TOKEN_RESERVES.toString()),
+          initialTokenSupply: new BN(DEFAULT_INITIAL_TOKEN_RESERVES.toString()),
+        },
+        null,
+        null,
+        null,
+        null,
+        null
+      )
+      .accounts({
+        mint: withdrawMint.publicKey,
+        creator: trader.publicKey,
+        feeRecipient: feeRecipient.publicKey,
+        program: program.programId,
+      })
+      .transaction();
 
-    let userPreSaleBalance = await getSPLBalance(
+    await sendTransaction(program, tx, [withdrawMint, trader], trader.publicKey);
+
+    await getOrCreateAssociatedTokenAccount(
       connection,
-      mint.publicKey,
-      tokenCreator.publicKey
+      trader,
+      withdrawMint.publicKey,
+      trader.publicKey
     );
 
-    let curvePreSaleBalance = await getSPLBalance(
+    let bondingCurveAccount = await program.account.bondingCurve.fetch(
+      withdrawBondingCurvePDA
+    );
+    let currentAMM = ammFromBondingCurve(
+      bondingCurveAccount,
+      BigInt(bondingCurveAccount.initialVirtualTokenReserves.toString())
+    );
+    let buyTokenAmount = currentAMM.realTokenReserves;
+    let maxSolAmount = currentAMM.getBuyPrice(buyTokenAmount);
+    maxSolAmount =
+      maxSolAmount +
+      calculateFee(maxSolAmount, Number(DEFAULT_FEE_BASIS_POINTS));
+
+    tx = await program.methods
+      .buy(new BN(buyTokenAmount.toString()), new BN(maxSolAmount.toString()))
+      .accounts({
+        user: trader.publicKey,
+        mint: withdrawMint.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        feeRecipient: feeRecipient.publicKey,
+        userPurchase: null,
+        program: program.programId,
+      })
+      .transaction();
+
+    let txResult = await sendTransaction(
+      program,
+      tx,
+      [trader],
+      trader.publicKey
+    );
+
+    let completeEvents = txResult.events.filter((event) => {
+      return event.name === "completeEvent";
+    });
+    assert.equal(completeEvents.length, 1);
+
+    //nothing is left for a pool
+    let bondingCurvePostSPLBalance = await getSPLBalance(
       connection,
-      mint.publicKey,
-      bondingCurvePDA,
+      withdrawMint.publicKey,
+      withdrawBondingCurvePDA,
       true
     );
+    assert.equal(bondingCurvePostSPLBalance, "0");
 
-    let feeRecipientPreBuySOLBalance = await connection.getBalance(
-      feeRecipient.publicKey
-    );
+    let errorCode = "";
+    try {
+      tx = await program.methods
+        .migrate()
+        .accounts({
+          user: trader.publicKey,
+          mint: withdrawMint.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          program: program.programId,
+        })
+        .transaction();
 
-    let txResult = await simpleSell(tokenCreator, tokenAmount, minSolAmount);
+      await sendTransaction(program, tx, [trader], trader.publicKey);
+    } catch (err) {
+      let anchorError = getAnchorError(err);
+      if (anchorError) {
+        errorCode = anchorError.error.errorCode.code;
+      }
+    }
+    assert.equal(errorCode, "InsufficientTokens");
+  });
 
-    let feeRecipientPostBuySOLBalance = await connection.getBalance(
+  it("can't withdraw as incorrect authority", async () => {
+    let errorCode = "";
+    try {
+      let tx = await program.methods
+        .withdraw()
+        .accounts({
+          user: tokenCreator.publicKey,
+          destination: tokenCreator.publicKey,
+          mint: mint.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          program: program.programId,
+        })
+        .transaction();
+
+      await sendTransaction(
+        program,
+        tx,
+        [tokenCreator],
+        tokenCreator.publicKey
+      );
+    } catch (err) {
+      let anchorError = getAnchorError(err);
+      if (anchorError) {
+        errorCode = anchorError.error.errorCode.code;
+      }
+    }
+    assert.equal(errorCode, "InvalidWithdrawAuthority");
+  });
+
+  it("can't withdraw without withdraw destinations", async () => {
+    let errorCode = "";
+    try {
+      let tx = await program.methods
+        .withdraw()
+        .accounts({
+          user: withdrawAuthority.publicKey,
+          destination: withdrawAuthority.publicKey,
+          mint: withdrawMint.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          program: program.programId,
+        })
+        .transaction();
+
+      await sendTransaction(
+        program,
+        tx,
+        [withdrawAuthority],
+        withdrawAuthority.publicKey
+      );
+    } catch (err) {
+      let anchorError = getAnchorError(err);
+      if (anchorError) {
+        errorCode = anchorError.error.errorCode.code;
+      }
+    }
+    assert.equal(errorCode, "WithdrawDestinationsNotSet");
+
+    await updateParams({
+      withdrawDestinations: {
+        count: 1,
+        destinations: [
+          withdrawAuthority.publicKey,
+          PublicKey.default,
+          PublicKey.default,
+          PublicKey.default,
+        ],
+      },
+    });
+  });
+
+  //it can withdraw
+  it("can withdraw", async () => {
+    let withdrawAuthorityPreSOLBalance = await connection.getBalance(
       feeRecipient.publicKey
     );
-    assert.equal(
-      feeRecipientPostBuySOLBalance - feeRecipientPreBuySOLBalance,
-      Number(fee)
+    let bondingCurvePreSOLBalance = await connection.getBalance(
+      withdrawBondingCurvePDA
     );
 
-    let tradeEvents = txResult.tx.events.filter((event) => {
-      return event.name === "tradeEvent";
-    });
-
-    let userPostSaleBalance = await getSPLBalance(
+    let bondingCurvePreSPLBalance = await getSPLBalance(
       connection,
-      mint.publicKey,
-      tokenCreator.publicKey
+      withdrawMint.publicKey,
+      withdrawBondingCurvePDA,
+      true
     );
 
-    assert.equal(
-      userPostSaleBalance,
-      (BigInt(userPreSaleBalance) - tokenAmount).toString()
+    let tx = await program.methods
+      .withdraw()
+      .accounts({
+        user: withdrawAuthority.publicKey,
+        destination: withdrawAuthority.publicKey,
+        mint: withdrawMint.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        program: program.programId,
+      })
+      .transaction();
+
+    let txResult = await sendTransaction(
+      program,
+      tx,
+      [withdrawAuthority],
+      withdrawAuthority.publicKey
     );
-    assert.equal(tradeEvents.length, 1);
 
-    let tradeEvent = toEvent("tradeEvent", tradeEvents[0]);
-    assert.notEqual(tradeEvent, null);
-    if (tradeEvent != null) {
-      assert.equal(tradeEvent.tokenAmount.toString(), tokenAmount.toString());
-      assert.equal(tradeEvent.isBuy, false);
+    let withdrawEvents = txResult.events.filter((event) => {
+      return event.name === "withdrawEvent";
+    });
+    assert.equal(withdrawEvents.length, 1);
+
+    let withdrawEvent = toEvent("withdrawEvent", withdrawEvents[0]);
+    assert.notEqual(withdrawEvent, null);
+    if (withdrawEvent != null) {
+      assert.equal(withdrawEvent.mint.toBase58(), withdrawMint.publicKey.toBase58());
       assert.equal(
-        tradeEvent.solAmount.toString(),
-        sellResults.sol_amount.toString()
+        withdrawEvent.recipient.toBase58(),
+        withdrawAuthority.publicKey.toBase58()
+      );
+      assert.equal(
+        withdrawEvent.tokenAmount.toString(),
+        bondingCurvePreSPLBalance
       );
     }
 
-    let curvePostSaleBalance = await getSPLBalance(
+    const [withdrawRecordPDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from("withdraw-record"), withdrawMint.publicKey.toBuffer()],
+      program.programId
+    );
+    let withdrawRecord = await program.account.withdrawRecord.fetch(
+      withdrawRecordPDA
+    );
+    assert.equal(withdrawRecord.mint.toBase58(), withdrawMint.publicKey.toBase58());
+    assert.equal(
+      withdrawRecord.tokensWithdrawn.toString(),
+      bondingCurvePreSPLBalance
+    );
+    assert.equal(
+      withdrawRecord.solWithdrawn.toString(),
+      withdrawEvent?.solAmount.toString()
+    );
+
+    let minBalanceRentExempt =
+      await connection.getMinimumBalanceForRentExemption(BONDING_CURVE_SIZE);
+    let bondingCurvePostSOLBalance = await connection.getBalance(
+      withdrawBondingCurvePDA
+    );
+
+    //confirm PDA only remaining balance is rent exempt
+    assert.equal(bondingCurvePostSOLBalance, minBalanceRentExempt);
+
+
+    //check if there is more SOL in withdraw authority then the bonding curve pre transfer
+    //TODO: Calculate the correct amount of SOL that should be in the withdraw authority
+    let withdrawAuthorityPostSOLBalance = await connection.getBalance(
+      withdrawAuthority.publicKey
+    );
+    let withdrawAuthorityBalanceDiff =
+      withdrawAuthorityPostSOLBalance - withdrawAuthorityPreSOLBalance;
+
+    let hasBalanceRisenMoreThenCurve =
+      withdrawAuthorityBalanceDiff - minBalanceRentExempt >
+      bondingCurvePreSOLBalance - minBalanceRentExempt;
+
+    assert.isTrue(hasBalanceRisenMoreThenCurve);
+
+    let withdrawAuthorityPostSPLBalance = await getSPLBalance(
       connection,
-      mint.publicKey,
-      bondingCurvePDA,
+      withdrawMint.publicKey,
+      withdrawAuthority.publicKey
+    );
+
+    let bondingCurvePostSPLBalance = await getSPLBalance(
+      connection,
+      withdrawMint.publicKey,
+      withdrawBondingCurvePDA,
       true
     );
 
+    assert.equal(withdrawAuthorityPostSPLBalance, bondingCurvePreSPLBalance);
+    assert.equal(bondingCurvePostSPLBalance, "0");
+
+    let bondingCurveAccount = await program.account.bondingCurve.fetch(
+      withdrawBondingCurvePDA
+    );
+
+    //confirm PDA has enough rent
+    assert.notEqual(bondingCurveAccount, null);
+  });
+
+  //param unit tests
+  it("can set params", async () => {
+    const randomFeeRecipient = anchor.web3.Keypair.generate();
+    const randomWithdrawAuthority = anchor.web3.Keypair.generate();
+
+    let txResult = await updateParams({
+      feeRecipient: randomFeeRecipient.publicKey,
+      withdrawAuthority: randomWithdrawAuthority.publicKey,
+      curveParams: {
+        initialVirtualTokenReserves: new BN(3000),
+        initialVirtualSolReserves: new BN(2000),
+        initialRealTokenReserves: new BN(1000),
+        initialTokenSupply: new BN(4000),
+      },
+      feeBasisPoints: new BN(100),
+    });
+
+    let global = await program.account.global.fetch(globalPDA);
+
+    let executeParamsEvents = txResult.events.filter((event) => {
+      return event.name === "executeParamsEvent";
+    });
+
+    assert.equal(executeParamsEvents.length, 1);
+
+    let executeParamsEvent = toEvent("executeParamsEvent", executeParamsEvents[0]);
+    assert.notEqual(executeParamsEvent, null);
+    if (executeParamsEvent != null) {
+      assert.equal(
+        executeParamsEvent.params.feeRecipient.toBase58(),
+        randomFeeRecipient.publicKey.toBase58()
+      );
+      assert.equal(
+        executeParamsEvent.params.withdrawAuthority.toBase58(),
+        randomWithdrawAuthority.publicKey.toBase58()
+      );
+      assert.equal(
+        executeParamsEvent.params.feeBasisPoints.toString(),
+        new BN(100).toString()
+      );
+    }
+
     assert.equal(
-      curvePostSaleBalance,
-      (BigInt(curvePreSaleBalance) + tokenAmount).toString()
+      global.feeRecipient.toBase58(),
+      randomFeeRecipient.publicKey.toBase58()
     );
-
-    let bondingCurveAccount = await program.account.bondingCurve.fetch(
-      bondingCurvePDA
+    assert.equal(
+      global.withdrawAuthority.toBase58(),
+      randomWithdrawAuthority.publicKey.toBase58()
     );
-    assertBondingCurve(currentAMM, bondingCurveAccount);
+    assert.equal(
+      global.initialVirtualTokenReserves.toString(),
+      new BN(3000).toString()
+    );
+    assert.equal(
+      global.initialVirtualSolReserves.toString(),
+      new BN(2000).toString()
+    );
+    assert.equal(
+      global.initialRealTokenReserves.toString(),
+      new BN(1000).toString()
+    );
+    assert.equal(global.initialTokenSupply.toString(), new BN(4000).toString());
+    assert.equal(global.feeBasisPoints.toString(), new BN(100).toString());
   });
 
-  //excpetion unit tests
-  it("can't withdraw as curve is incomplete", async () => {
+  it("can't set params as non-authority", async () => {
     let errorCode = "";
     try {
-      let tx = await program.methods
-        .withdraw()
+      const randomFeeRecipient = anchor.web3.Keypair.generate();
+      const randomWithdrawAuthority = anchor.web3.Keypair.generate();
+
+      await program.methods
+        .setParams(
+          randomFeeRecipient.publicKey,
+          randomWithdrawAuthority.publicKey,
+          new BN(1000),
+          new BN(2000),
+          new BN(3000),
+          new BN(4000),
+          new BN(100)
+        )
         .accounts({
-          user: withdrawAuthority.publicKey,
-          mint: mint.publicKey,
+          user: tokenCreator.publicKey,
+          program: program.programId,
         })
-        .transaction();
-
-      await sendTransaction(
-        program,
-        tx,
-        [withdrawAuthority],
-        withdrawAuthority.publicKey
-      );
+        .signers([tokenCreator])
+        .rpc();
     } catch (err) {
       let anchorError = getAnchorError(err);
       if (anchorError) {
         errorCode = anchorError.error.errorCode.code;
       }
     }
-    assert.equal(errorCode, "BondingCurveNotComplete");
+    assert.equal(errorCode, "InvalidAuthority");
   });
 
-  it("can't buy a token, not enough SOL", async () => {
-    const notEnoughSolUser = anchor.web3.Keypair.generate();
-
-    await fundAccountSOL(
-      connection,
-      notEnoughSolUser.publicKey,
-      0.021 * LAMPORTS_PER_SOL
-    );
-
+  it("can't set a fee schedule with unsorted tiers", async () => {
     let errorCode = "";
     try {
-      await simpleBuy(
-        notEnoughSolUser,
-        5_000_000_000_000n,
-        BigInt(5 * LAMPORTS_PER_SOL)
-      );
+      await queueParams({
+        feeSchedule: {
+          kind: { progress: {} },
+          tierCount: 2,
+          tiers: [
+            { threshold: new BN(5000), feeBasisPoints: new BN(100) },
+            { threshold: new BN(1000), feeBasisPoints: new BN(500) },
+            { threshold: new BN(0), feeBasisPoints: new BN(0) },
+            { threshold: new BN(0), feeBasisPoints: new BN(0) },
+          ],
+        },
+      });
     } catch (err) {
       let anchorError = getAnchorError(err);
       if (anchorError) {
         errorCode = anchorError.error.errorCode.code;
       }
     }
-    assert.equal(errorCode, "InsufficientSOL");
+    assert.equal(errorCode, "InvalidFeeSchedule");
   });
 
-  it("can't buy a token, exceed max sol", async () => {
+  it("can't set a fee above 100%", async () => {
     let errorCode = "";
     try {
-      await simpleBuy(tokenCreator, DEFAULT_TOKEN_BALANCE / 100n, 1n);
+      await queueParams({ feeBasisPoints: new BN(10001) });
     } catch (err) {
       let anchorError = getAnchorError(err);
       if (anchorError) {
         errorCode = anchorError.error.errorCode.code;
       }
     }
-    assert.equal(errorCode, "MaxSOLCostExceeded");
+    assert.equal(errorCode, "InvalidFeeBasisPoints");
   });
 
-  it("can't buy 0 tokens", async () => {
-    let errorCode = "";
-    try {
-      await simpleBuy(tokenCreator, 0n, 1n);
-    } catch (err) {
-      let anchorError = getAnchorError(err);
-      if (anchorError) {
-        errorCode = anchorError.error.errorCode.code;
+  it("can't allow an empty or unknown set of curve kinds", async () => {
+    for (const allowedCurveKinds of [0, 0b1_0000]) {
+      let errorCode = "";
+      try {
+        await queueParams({ allowedCurveKinds });
+      } catch (err) {
+        let anchorError = getAnchorError(err);
+        if (anchorError) {
+          errorCode = anchorError.error.errorCode.code;
+        }
       }
+      assert.equal(errorCode, "InvalidCurveKinds");
     }
-    assert.equal(errorCode, "MinBuy");
   });
 
-  it("can't sell a token, not enough tokens", async () => {
+  it("can't set curve defaults with more real than virtual token reserves", async () => {
     let errorCode = "";
     try {
-      await simpleSell(tokenCreator, DEFAULT_TOKEN_BALANCE, 0n);
+      await queueParams({
+        curveParams: {
+          initialVirtualTokenReserves: new BN(1000),
+          initialVirtualSolReserves: new BN(2000),
+          initialRealTokenReserves: new BN(3000),
+          initialTokenSupply: new BN(4000),
+        },
+      });
     } catch (err) {
       let anchorError = getAnchorError(err);
       if (anchorError) {
         errorCode = anchorError.error.errorCode.code;
       }
     }
-    assert.equal(errorCode, "InsufficientTokens");
+    assert.equal(errorCode, "InvalidCurveParams");
   });
 
-  it("can't sell 0 tokens", async () => {
-    let errorCode = "";
-    try {
-      await simpleSell(tokenCreator, 0n, 0n);
-    } catch (err) {
-      let anchorError = getAnchorError(err);
-      if (anchorError) {
-        errorCode = anchorError.error.errorCode.code;
-      }
-    }
-    assert.equal(errorCode, "MinSell");
+  it("can set the withdraw authority", async () => {
+    let txResult = await updateParams({
+      withdrawAuthority: withdrawAuthority.publicKey,
+    });
+
+    let executeParamsEvents = txResult.events.filter((event) => {
+      return event.name === "executeParamsEvent";
+    });
+    assert.equal(executeParamsEvents.length, 1);
+
+    let global = await program.account.global.fetch(globalPDA);
+    assert.equal(
+      global.withdrawAuthority.toBase58(),
+      withdrawAuthority.publicKey.toBase58()
+    );
   });
 
-  it("can't sell a token, exceed mint sol sell", async () => {
+  it("can transfer the authority in two steps", async () => {
+    const newAuthority = anchor.web3.Keypair.generate();
+    await fundAccountSOL(connection, newAuthority.publicKey, LAMPORTS_PER_SOL);
+
+    let tx = await program.methods
+      .proposeAuthority(newAuthority.publicKey)
+      .accounts({
+        user: authority.publicKey,
+        program: program.programId,
+      })
+      .transaction();
+
+    let txResult = await sendTransaction(
+      program,
+      tx,
+      [authority],
+      authority.publicKey
+    );
+
+    let proposeEvents = txResult.events.filter((event) => {
+      return event.name === "proposeAuthorityEvent";
+    });
+    assert.equal(proposeEvents.length, 1);
+
+    let global = await program.account.global.fetch(globalPDA);
+    assert.equal(global.authority.toBase58(), authority.publicKey.toBase58());
+    assert.equal(
+      global.pendingAuthority.toBase58(),
+      newAuthority.publicKey.toBase58()
+    );
+
+    //only the pending authority can accept
     let errorCode = "";
     try {
-      await simpleSell(tokenCreator, 1n, DEFAULT_TOKEN_BALANCE);
+      await program.methods
+        .acceptAuthority()
+        .accounts({
+          user: tokenCreator.publicKey,
+          program: program.programId,
+        })
+        .signers([tokenCreator])
+        .rpc();
     } catch (err) {
       let anchorError = getAnchorError(err);
       if (anchorError) {
         errorCode = anchorError.error.errorCode.code;
       }
     }
-    assert.equal(errorCode, "MinSOLOutputExceeded");
-  });
-
-  //curve complete unit tests
-  it("can complete the curve", async () => {
-    let currentAMM = await getAmmFromBondingCurve();
-    let buyTokenAmount = currentAMM.realTokenReserves;
-    let maxSolAmount = currentAMM.getBuyPrice(buyTokenAmount);
+    assert.equal(errorCode, "InvalidAuthority");
 
-    maxSolAmount =
-      maxSolAmount +
-      calculateFee(maxSolAmount, Number(DEFAULT_FEE_BASIS_POINTS));
-    let buyResult = currentAMM.applyBuy(buyTokenAmount);
+    tx = await program.methods
+      .acceptAuthority()
+      .accounts({
+        user: newAuthority.publicKey,
+        program: program.programId,
+      })
+      .transaction();
 
-    let userPrePurchaseBalance = await getSPLBalance(
-      connection,
-      mint.publicKey,
-      tokenCreator.publicKey
+    txResult = await sendTransaction(
+      program,
+      tx,
+      [newAuthority],
+      newAuthority.publicKey
     );
 
-    let txResult = await simpleBuy(tokenCreator, buyTokenAmount, maxSolAmount);
-
-    let tradeEvents = txResult.tx.events.filter((event) => {
-      return event.name === "tradeEvent";
+    let acceptEvents = txResult.events.filter((event) => {
+      return event.name === "acceptAuthorityEvent";
     });
-    assert.equal(tradeEvents.length, 1);
+    assert.equal(acceptEvents.length, 1);
 
-    let tradeEvent = toEvent("tradeEvent", tradeEvents[0]);
-    assert.notEqual(tradeEvent, null);
-    if (tradeEvent != null) {
-      assert.equal(tradeEvent.isBuy, true);
+    let acceptEvent = toEvent("acceptAuthorityEvent", acceptEvents[0]);
+    assert.notEqual(acceptEvent, null);
+    if (acceptEvent != null) {
       assert.equal(
-        tradeEvent.solAmount.toString(),
-        buyResult.sol_amount.toString()
+        acceptEvent.oldAuthority.toBase58(),
+        authority.publicKey.toBase58()
+      );
+      assert.equal(
+        acceptEvent.newAuthority.toBase58(),
+        newAuthority.publicKey.toBase58()
       );
     }
 
-    let userPostPurchaseBalance = await getSPLBalance(
-      connection,
-      mint.publicKey,
-      tokenCreator.publicKey
-    );
-
-    assert.equal(
-      userPostPurchaseBalance,
-      (BigInt(userPrePurchaseBalance) + buyTokenAmount).toString()
-    );
-
-    let bondingCurveTokenAccountInfo = await connection.getTokenAccountBalance(
-      txResult.bondingCurveTokenAccount
-    );
-
-    assert.equal(
-      (
-        BigInt(bondingCurveTokenAccountInfo.value.amount) +
-        DEFAULT_INITIAL_TOKEN_RESERVES
-      ).toString(),
-      DEFAULT_TOKEN_BALANCE.toString()
-    );
+    global = await program.account.global.fetch(globalPDA);
+    assert.equal(global.authority.toBase58(), newAuthority.publicKey.toBase58());
+    assert.equal(global.pendingAuthority.toBase58(), PublicKey.default.toBase58());
 
-    let bondingCurveAccount = await program.account.bondingCurve.fetch(
-      bondingCurvePDA
-    );
+    //hand the authority back for the remaining tests
+    await program.methods
+      .proposeAuthority(authority.publicKey)
+      .accounts({
+        user: newAuthority.publicKey,
+        program: program.programId,
+      })
+      .signers([newAuthority])
+      .rpc();
 
-    assertBondingCurve(currentAMM, bondingCurveAccount, true);
+    await program.methods
+      .acceptAuthority()
+      .accounts({
+        user: authority.publicKey,
+        program: program.programId,
+      })
+      .signers([authority])
+      .rpc();
   });
 
-  it("can't buy a token, curve complete", async () => {
-    let currentAMM = await getAmmFromBondingCurve();
-
-    let buyTokenAmount = 100n;
-    let maxSolAmount = currentAMM.getBuyPrice(buyTokenAmount);
-
+  it("can't withdraw again before the withdraw interval passes", async () => {
     let errorCode = "";
     try {
-      await simpleBuy(tokenCreator, buyTokenAmount, maxSolAmount);
+      await queueParams({
+        withdrawMinIntervalSeconds: new BN(0),
+        withdrawWindowSeconds: new BN(3600),
+        withdrawWindowSolCap: new BN(0),
+      });
     } catch (err) {
       let anchorError = getAnchorError(err);
       if (anchorError) {
         errorCode = anchorError.error.errorCode.code;
       }
     }
-    assert.equal(errorCode, "BondingCurveComplete");
-  });
+    assert.equal(errorCode, "InvalidWithdrawLimits");
 
-  it("can't sell a token, curve complete", async () => {
-    let tokenAmount = 100n;
-    let minSolAmount = 0n;
+    let txResult = await updateParams({
+      withdrawMinIntervalSeconds: new BN(3600),
+      withdrawWindowSeconds: new BN(86400),
+      withdrawWindowSolCap: new BN(LAMPORTS_PER_SOL),
+    });
 
-    let errorCode = "";
+    let executeParamsEvents = txResult.events.filter((event) => {
+      return event.name === "executeParamsEvent";
+    });
+    assert.equal(executeParamsEvents.length, 1);
+
+    let global = await program.account.global.fetch(globalPDA);
+    assert.equal(global.withdrawLimits.minIntervalSeconds.toString(), "3600");
+    assert.equal(
+      global.withdrawLimits.windowSolCap.toString(),
+      LAMPORTS_PER_SOL.toString()
+    );
+
+    //the curve was withdrawn earlier in the suite
+    errorCode = "";
     try {
-      await simpleSell(tokenCreator, tokenAmount, minSolAmount);
+      let tx = await program.methods
+        .withdraw()
+        .accounts({
+          user: withdrawAuthority.publicKey,
+          destination: withdrawAuthority.publicKey,
+          mint: withdrawMint.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          program: program.programId,
+        })
+        .transaction();
+
+      await sendTransaction(
+        program,
+        tx,
+        [withdrawAuthority],
+        withdrawAuthority.publicKey
+      );
     } catch (err) {
       let anchorError = getAnchorError(err);
       if (anchorError) {
         errorCode = anchorError.error.errorCode.code;
       }
     }
-    assert.equal(errorCode, "BondingCurveComplete");
+    assert.equal(errorCode, "WithdrawCooldown");
+
+    await updateParams({
+      withdrawMinIntervalSeconds: new BN(0),
+      withdrawWindowSeconds: new BN(0),
+      withdrawWindowSolCap: new BN(0),
+    });
   });
 
-  it("can't withdraw as incorrect authority", async () => {
+  it("can only withdraw to an allowlisted destination", async () => {
+    const treasury = anchor.web3.Keypair.generate();
+
+    let txResult = await updateParams({
+      withdrawDestinations: {
+        count: 1,
+        destinations: [
+          treasury.publicKey,
+          PublicKey.default,
+          PublicKey.default,
+          PublicKey.default,
+        ],
+      },
+    });
+
+    let executeParamsEvents = txResult.events.filter((event) => {
+      return event.name === "executeParamsEvent";
+    });
+    assert.equal(executeParamsEvents.length, 1);
+
+    //the withdraw authority's own wallet is no longer allowed
     let errorCode = "";
     try {
       let tx = await program.methods
         .withdraw()
         .accounts({
-          user: tokenCreator.publicKey,
-          mint: mint.publicKey,
+          user: withdrawAuthority.publicKey,
+          destination: withdrawAuthority.publicKey,
+          mint: withdrawMint.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          program: program.programId,
         })
         .transaction();
 
       await sendTransaction(
         program,
         tx,
-        [tokenCreator],
-        tokenCreator.publicKey
+        [withdrawAuthority],
+        withdrawAuthority.publicKey
       );
     } catch (err) {
       let anchorError = getAnchorError(err);
@@ -722,202 +3622,250 @@ describe("curve-launchpad", () => {
         errorCode = anchorError.error.errorCode.code;
       }
     }
-    assert.equal(errorCode, "InvalidWithdrawAuthority");
-  });
-
-  //it can withdraw
-  it("can withdraw", async () => {
-    let withdrawAuthorityPreSOLBalance = await connection.getBalance(
-      feeRecipient.publicKey
-    );
-    let bondingCurvePreSOLBalance = await connection.getBalance(
-      bondingCurvePDA
-    );
-
-    let bondingCurvePreSPLBalance = await getSPLBalance(
-      connection,
-      mint.publicKey,
-      bondingCurvePDA,
-      true
-    );
+    assert.equal(errorCode, "InvalidWithdrawDestination");
 
     let tx = await program.methods
       .withdraw()
       .accounts({
         user: withdrawAuthority.publicKey,
-        mint: mint.publicKey,
+        destination: treasury.publicKey,
+        mint: withdrawMint.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        program: program.programId,
       })
       .transaction();
 
-    await sendTransaction(
+    txResult = await sendTransaction(
       program,
       tx,
       [withdrawAuthority],
       withdrawAuthority.publicKey
     );
 
-    let minBalanceRentExempt =
-      await connection.getMinimumBalanceForRentExemption(8 + 41);
-    let bondingCurvePostSOLBalance = await connection.getBalance(
-      bondingCurvePDA
-    );
-
-    //confirm PDA only remaining balance is rent exempt
-    assert.equal(bondingCurvePostSOLBalance, minBalanceRentExempt);
-
-
-    //check if there is more SOL in withdraw authority then the bonding curve pre transfer
-    //TODO: Calculate the correct amount of SOL that should be in the withdraw authority
-    let withdrawAuthorityPostSOLBalance = await connection.getBalance(
-      withdrawAuthority.publicKey
-    );
-    let withdrawAuthorityBalanceDiff =
-      withdrawAuthorityPostSOLBalance - withdrawAuthorityPreSOLBalance;
+    let withdrawEvents = txResult.events.filter((event) => {
+      return event.name === "withdrawEvent";
+    });
+    assert.equal(withdrawEvents.length, 1);
 
-    let hasBalanceRisenMoreThenCurve =
-      withdrawAuthorityBalanceDiff - minBalanceRentExempt >
-      bondingCurvePreSOLBalance - minBalanceRentExempt;
+    let withdrawEvent = toEvent("withdrawEvent", withdrawEvents[0]);
+    assert.notEqual(withdrawEvent, null);
+    if (withdrawEvent != null) {
+      assert.equal(
+        withdrawEvent.recipient.toBase58(),
+        treasury.publicKey.toBase58()
+      );
+      assert.equal(
+        withdrawEvent.user.toBase58(),
+        withdrawAuthority.publicKey.toBase58()
+      );
+    }
 
-    assert.isTrue(hasBalanceRisenMoreThenCurve);
+    //the allowlist can't be cleared
+    errorCode = "";
+    try {
+      await queueParams({
+        withdrawDestinations: {
+          count: 0,
+          destinations: [
+            PublicKey.default,
+            PublicKey.default,
+            PublicKey.default,
+            PublicKey.default,
+          ],
+        },
+      });
+    } catch (err) {
+      let anchorError = getAnchorError(err);
+      if (anchorError) {
+        errorCode = anchorError.error.errorCode.code;
+      }
+    }
+    assert.equal(errorCode, "InvalidWithdrawDestinations");
+
+    await updateParams({
+      withdrawDestinations: {
+        count: 1,
+        destinations: [
+          withdrawAuthority.publicKey,
+          PublicKey.default,
+          PublicKey.default,
+          PublicKey.default,
+        ],
+      },
+    });
+  });
 
-    let withdrawAuthorityPostSPLBalance = await getSPLBalance(
-      connection,
-      mint.publicKey,
-      withdrawAuthority.publicKey
-    );
+  it("can queue, execute and cancel params behind the timelock", async () => {
+    const feeSchedule = {
+      kind: { flat: {} },
+      tierCount: 0,
+      tiers: [
+        { threshold: new BN(0), feeBasisPoints: new BN(0) },
+        { threshold: new BN(0), feeBasisPoints: new BN(0) },
+        { threshold: new BN(0), feeBasisPoints: new BN(0) },
+        { threshold: new BN(0), feeBasisPoints: new BN(0) },
+      ],
+    };
 
-    let bondingCurvePostSPLBalance = await getSPLBalance(
-      connection,
-      mint.publicKey,
-      bondingCurvePDA,
-      true
-    );
+    let txResult = await updateParams({
+      feeBasisPoints: new BN(75),
+      feeSchedule,
+    });
 
-    assert.equal(withdrawAuthorityPostSPLBalance, bondingCurvePreSPLBalance);
-    assert.equal(bondingCurvePostSPLBalance, "0");
+    let executeEvents = txResult.events.filter((event) => {
+      return event.name === "executeParamsEvent";
+    });
+    assert.equal(executeEvents.length, 1);
 
-    let bondingCurveAccount = await program.account.bondingCurve.fetch(
-      bondingCurvePDA
-    );
+    let global = await program.account.global.fetch(globalPDA);
+    assert.equal(global.feeBasisPoints.toString(), "75");
+    assert.equal(await connection.getAccountInfo(pendingParamsPDA), null);
 
-    //confirm PDA has enough rent
-    assert.notEqual(bondingCurveAccount, null);
-  });
+    //the timelock is capped so queued changes can always execute
+    let errorCode = "";
+    try {
+      await program.methods
+        .setParamsTimelock(new BN(31 * 24 * 60 * 60))
+        .accounts({
+          user: authority.publicKey,
+          program: program.programId,
+        })
+        .signers([authority])
+        .rpc();
+    } catch (err) {
+      let anchorError = getAnchorError(err);
+      if (anchorError) {
+        errorCode = anchorError.error.errorCode.code;
+      }
+    }
+    assert.equal(errorCode, "InvalidTimelock");
 
-  //param unit tests
-  it("can set params", async () => {
-    const randomFeeRecipient = anchor.web3.Keypair.generate();
-    const randomWithdrawAuthority = anchor.web3.Keypair.generate();
+    //and can't be turned off
+    errorCode = "";
+    try {
+      await program.methods
+        .setParamsTimelock(new BN(0))
+        .accounts({
+          user: authority.publicKey,
+          program: program.programId,
+        })
+        .signers([authority])
+        .rpc();
+    } catch (err) {
+      let anchorError = getAnchorError(err);
+      if (anchorError) {
+        errorCode = anchorError.error.errorCode.code;
+      }
+    }
+    assert.equal(errorCode, "InvalidTimelock");
 
-    let tx = await program.methods
-      .setParams(
-        randomFeeRecipient.publicKey,
-        randomWithdrawAuthority.publicKey,
-        new BN(1000),
-        new BN(2000),
-        new BN(3000),
-        new BN(4000),
-        new BN(100)
-      )
+    await program.methods
+      .setParamsTimelock(new BN(3600))
       .accounts({
         user: authority.publicKey,
         program: program.programId,
       })
-      .transaction();
+      .signers([authority])
+      .rpc();
 
-    let txResult = await sendTransaction(
-      program,
-      tx,
-      [authority],
-      authority.publicKey
-    );
+    //instant setters are disabled while the timelock is on
+    errorCode = "";
+    try {
+      await program.methods
+        .setFeeConfig(feeRecipient.publicKey, new BN(10000))
+        .accounts({
+          user: authority.publicKey,
+          program: program.programId,
+        })
+        .signers([authority])
+        .rpc();
+    } catch (err) {
+      let anchorError = getAnchorError(err);
+      if (anchorError) {
+        errorCode = anchorError.error.errorCode.code;
+      }
+    }
+    assert.equal(errorCode, "TimelockEnabled");
 
-    let global = await program.account.global.fetch(globalPDA);
+    errorCode = "";
+    try {
+      await program.methods
+        .setFeeSchedule(feeSchedule)
+        .accounts({
+          user: authority.publicKey,
+          program: program.programId,
+        })
+        .signers([authority])
+        .rpc();
+    } catch (err) {
+      let anchorError = getAnchorError(err);
+      if (anchorError) {
+        errorCode = anchorError.error.errorCode.code;
+      }
+    }
+    assert.equal(errorCode, "TimelockEnabled");
 
-    let setParamsEvents = txResult.events.filter((event) => {
-      return event.name === "setParamsEvent";
-    });
+    errorCode = "";
+    try {
+      await program.methods
+        .setCreatorFee(new BN(10000))
+        .accounts({
+          user: authority.publicKey,
+          program: program.programId,
+        })
+        .signers([authority])
+        .rpc();
+    } catch (err) {
+      let anchorError = getAnchorError(err);
+      if (anchorError) {
+        errorCode = anchorError.error.errorCode.code;
+      }
+    }
+    assert.equal(errorCode, "TimelockEnabled");
 
-    assert.equal(setParamsEvents.length, 1);
+    errorCode = "";
+    try {
+      await program.methods
+        .setWithdrawLimits(new BN(0), new BN(0), new BN(0))
+        .accounts({
+          user: authority.publicKey,
+          program: program.programId,
+        })
+        .signers([authority])
+        .rpc();
+    } catch (err) {
+      let anchorError = getAnchorError(err);
+      if (anchorError) {
+        errorCode = anchorError.error.errorCode.code;
+      }
+    }
+    assert.equal(errorCode, "TimelockEnabled");
 
-    let setParamsEvent = toEvent("setParamsEvent", setParamsEvents[0]);
-    assert.notEqual(setParamsEvent, null);
-    if (setParamsEvent != null) {
-      assert.equal(
-        setParamsEvent.feeRecipient.toBase58(),
-        randomFeeRecipient.publicKey.toBase58()
-      );
-      assert.equal(
-        setParamsEvent.withdrawAuthority.toBase58(),
-        randomWithdrawAuthority.publicKey.toBase58()
-      );
-      assert.equal(
-        setParamsEvent.initialVirtualTokenReserves.toString(),
-        new BN(1000).toString()
-      );
-      assert.equal(
-        setParamsEvent.initialVirtualSolReserves.toString(),
-        new BN(2000).toString()
-      );
-      assert.equal(
-        setParamsEvent.initialRealTokenReserves.toString(),
-        new BN(3000).toString()
-      );
-      assert.equal(
-        setParamsEvent.initialTokenSupply.toString(),
-        new BN(4000).toString()
-      );
+    txResult = await queueParams({ feeBasisPoints: new BN(10000) });
+
+    let queueEvents = txResult.events.filter((event) => {
+      return event.name === "queueParamsEvent";
+    });
+    assert.equal(queueEvents.length, 1);
+
+    let queueEvent = toEvent("queueParamsEvent", queueEvents[0]);
+    assert.notEqual(queueEvent, null);
+    if (queueEvent != null) {
       assert.equal(
-        setParamsEvent.feeBasisPoints.toString(),
-        new BN(100).toString()
+        queueEvent.executeAfter.sub(queueEvent.queuedAt).toString(),
+        "3600"
       );
     }
 
-    assert.equal(
-      global.feeRecipient.toBase58(),
-      randomFeeRecipient.publicKey.toBase58()
-    );
-    assert.equal(
-      global.withdrawAuthority.toBase58(),
-      randomWithdrawAuthority.publicKey.toBase58()
-    );
-    assert.equal(
-      global.initialVirtualTokenReserves.toString(),
-      new BN(1000).toString()
-    );
-    assert.equal(
-      global.initialVirtualSolReserves.toString(),
-      new BN(2000).toString()
-    );
-    assert.equal(
-      global.initialRealTokenReserves.toString(),
-      new BN(3000).toString()
-    );
-    assert.equal(global.initialTokenSupply.toString(), new BN(4000).toString());
-    assert.equal(global.feeBasisPoints.toString(), new BN(100).toString());
-  });
-
-  it("can't set params as non-authority", async () => {
-    let errorCode = "";
+    errorCode = "";
     try {
-      const randomFeeRecipient = anchor.web3.Keypair.generate();
-      const randomWithdrawAuthority = anchor.web3.Keypair.generate();
-
       await program.methods
-        .setParams(
-          randomFeeRecipient.publicKey,
-          randomWithdrawAuthority.publicKey,
-          new BN(1000),
-          new BN(2000),
-          new BN(3000),
-          new BN(4000),
-          new BN(100)
-        )
+        .executeParams()
         .accounts({
-          user: tokenCreator.publicKey,
+          user: authority.publicKey,
           program: program.programId,
         })
-        .signers([tokenCreator])
+        .signers([authority])
         .rpc();
     } catch (err) {
       let anchorError = getAnchorError(err);
@@ -925,7 +3873,31 @@ describe("curve-launchpad", () => {
         errorCode = anchorError.error.errorCode.code;
       }
     }
-    assert.equal(errorCode, "InvalidAuthority");
+    assert.equal(errorCode, "TimelockNotElapsed");
+
+    let tx = await program.methods
+      .cancelParams()
+      .accounts({
+        user: authority.publicKey,
+        program: program.programId,
+      })
+      .transaction();
+
+    txResult = await sendTransaction(
+      program,
+      tx,
+      [authority],
+      authority.publicKey
+    );
+
+    let cancelEvents = txResult.events.filter((event) => {
+      return event.name === "cancelParamsEvent";
+    });
+    assert.equal(cancelEvents.length, 1);
+
+    global = await program.account.global.fetch(globalPDA);
+    assert.equal(global.feeBasisPoints.toString(), "75");
+    assert.equal(await connection.getAccountInfo(pendingParamsPDA), null);
   });
 });
 
@@ -16,10 +16,36 @@ import { getAssociatedTokenAddressSync } from "@solana/spl-token";
 type EventKeys = keyof anchor.IdlEvents<CurveLaunchpad>;
 
 const validEventNames: Array<keyof anchor.IdlEvents<CurveLaunchpad>> = [
+  "acceptAuthorityEvent",
+  "cancelParamsEvent",
+  "claimCreatorFeesEvent",
   "completeEvent",
   "createEvent",
+  "executeParamsEvent",
+  "liquidityEvent",
+  "metadataUpdatedEvent",
+  "migrateEvent",
+  "pauseEvent",
+  "proposeAuthorityEvent",
+  "queueParamsEvent",
+  "setAllowedCurveKindsEvent",
+  "setCreatorFeeEvent",
+  "setCurveDefaultsEvent",
+  "setCurveParamBoundsEvent",
+  "setDecimalsRangeEvent",
+  "setFeeConfigEvent",
+  "setFeeScheduleEvent",
   "setParamsEvent",
+  "setParamsTimelockEvent",
+  "setPauserEvent",
+  "setWithdrawAuthorityEvent",
+  "setWithdrawDestinationsEvent",
+  "setWithdrawLimitsEvent",
   "tradeEvent",
+  "tradeFeeEvent",
+  "upgradeBondingCurveEvent",
+  "upgradeGlobalEvent",
+  "withdrawEvent",
 ];
 
 export const getTransactionEvents = (
@@ -183,6 +209,17 @@ export const ammFromBondingCurve = (
   );
 };
 
+export const poolAmmFromPool = (
+  poolAccount: anchor.IdlAccounts<CurveLaunchpad>["pool"] | null
+) => {
+  if(!poolAccount) throw new Error("Pool account not found");
+  return new client.PoolAMM(
+    BigInt(poolAccount.solReserves.toString()),
+    BigInt(poolAccount.tokenReserves.toString()),
+    BigInt(poolAccount.lpSupply.toString())
+  );
+};
+
 export const bigIntToSOL = (amount: bigint) => {
   return amount / BigInt(LAMPORTS_PER_SOL);
 }